@@ -0,0 +1,642 @@
+// Command line ballistic calculator
+// Everything taken as flags, or from a profile with flags on top - quantities are given with their
+// units, ie, --velocity "2710 ft/s", or as plain numbers in the default unit listed in USAGE
+// Profiles need the profile feature, ie, cargo install --features profile - flags always work
+
+use point_mass_ballistics::{
+    card::Row,
+    export::{write_csv, write_json, Column, Metadata},
+    output::Measurements,
+    projectiles::{Projectile, ProjectileImpl, G1, G2, G5, G6, G7, G8, GI, GS},
+    simulation::SimulationBuilder,
+    solvers::{Observation, Solution},
+    units::{
+        angle, degree, energy, fahrenheit, foot_per_second, foot_pound, grain, inch,
+        inch_of_mercury, joule, length, length::centimeter, meter, meter_per_second, mil,
+        mile_per_hour, moa, radian, second, velocity, yard, Angle, Energy, Length, Mass, Pressure,
+        ThermodynamicTemperature, Velocity,
+    },
+    Numeric,
+};
+
+#[cfg(feature = "profile")]
+use point_mass_ballistics::profile::Profile;
+
+use std::{
+    collections::HashMap, env, error, fmt, io, marker::PhantomData, ops::DerefMut, process,
+    str::FromStr,
+};
+
+const USAGE: &str = "\
+usage: ballistics <zero|card|solution> [options]
+
+  zero       scope angles for the zero
+  card       range card from --start to --end every --step
+  solution   holds and clicks for a single shot at --range
+
+profile:
+  --profile FILE         TOML rifle, load and optic profile, flags below override it
+
+projectile:
+  --drag TABLE           G1, G2, G5, G6, G7 (default), G8, GI or GS
+  --caliber LENGTH       in
+  --weight MASS          gr
+  --bc NUMBER
+  --velocity VELOCITY    ft/s, muzzle velocity
+
+rifle:
+  --scope-height LENGTH  in
+  --zero LENGTH          yd, zero distance (default 100 yd)
+
+conditions (shot only, zero is under standard conditions):
+  --temperature TEMP     °F
+  --pressure PRESSURE    in Hg
+  --humidity NUMBER      0 to 1
+  --wind-speed VELOCITY  mi/h
+  --wind-angle ANGLE     °, 90 is wind from the right
+  --shot-angle ANGLE     °, line of sight above (or below) the horizon
+  --cant ANGLE           °
+  --bearing ANGLE        °, 0 is north
+  --latitude ANGLE       °
+
+shot:
+  --range LENGTH         yd, for solution
+  --start LENGTH         yd, for card (default 0 yd)
+  --end LENGTH           yd, for card (default 1000 yd)
+  --step LENGTH          yd, for card (default 100 yd)
+  --tolerance LENGTH     in (default 0.001 in)
+
+output:
+  --units UNITS          imperial (default) or metric
+  --angles ANGLES        moa (default) or mil
+  --format FORMAT        text (default), csv or json
+";
+
+// Every option listed in USAGE
+const FLAGS: &[&str] = &[
+    "profile",
+    "drag",
+    "caliber",
+    "weight",
+    "bc",
+    "velocity",
+    "scope-height",
+    "zero",
+    "temperature",
+    "pressure",
+    "humidity",
+    "wind-speed",
+    "wind-angle",
+    "shot-angle",
+    "cant",
+    "bearing",
+    "latitude",
+    "range",
+    "start",
+    "end",
+    "step",
+    "tolerance",
+    "units",
+    "angles",
+    "format",
+];
+
+// Old spellings, still accepted
+const ALIASES: &[(&str, &str)] = &[("lattitude", "latitude")];
+
+type Result<T> = std::result::Result<T, Box<dyn error::Error>>;
+
+// Errors from the command line itself, rather than the simulation
+#[derive(Debug)]
+struct Usage(String);
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl error::Error for Usage {}
+
+fn usage<T>(message: String) -> Result<T> {
+    Err(Box::new(Usage(message)))
+}
+
+// Command, then --flag value pairs
+struct Args {
+    command: String,
+    flags: HashMap<String, String>,
+}
+impl Args {
+    fn parse() -> Result<Self> {
+        let mut args = env::args().skip(1);
+        let command = match args.next() {
+            Some(command) if command != "-h" && command != "--help" => command,
+            _ => return usage(String::new()),
+        };
+        let mut flags = HashMap::new();
+        while let Some(flag) = args.next() {
+            let name = flag.strip_prefix("--").map(|name| {
+                ALIASES
+                    .iter()
+                    .find(|(alias, _)| *alias == name)
+                    .map_or(name, |(_, name)| name)
+            });
+            let name = match name {
+                Some(name) if FLAGS.contains(&name) => name.to_string(),
+                _ => return usage(format!("unknown option {:?}", flag)),
+            };
+            let value = match args.next() {
+                Some(value) => value,
+                None => return usage(format!("missing value for {:?}", flag)),
+            };
+            flags.insert(name, value);
+        }
+        Ok(Self { command, flags })
+    }
+    fn text(&self, name: &str) -> Option<&str> {
+        self.flags.get(name).map(String::as_str)
+    }
+    fn number(&self, name: &str) -> Result<Option<Numeric>> {
+        match self.text(name) {
+            Some(value) => match value.trim().parse() {
+                Ok(value) => Ok(Some(value)),
+                Err(_) => usage(format!("--{} expects a number, got {:?}", name, value)),
+            },
+            None => Ok(None),
+        }
+    }
+    // Quantity with its unit, or a plain number in the default unit
+    fn quantity<Q>(&self, name: &str, default: fn(Numeric) -> Q) -> Result<Option<Q>>
+    where
+        Q: FromStr,
+    {
+        match self.text(name) {
+            Some(value) => match (value.parse(), value.trim().parse()) {
+                (Ok(value), _) => Ok(Some(value)),
+                (_, Ok(value)) => Ok(Some(default(value))),
+                _ => usage(format!("--{} has an unknown unit, got {:?}", name, value)),
+            },
+            None => Ok(None),
+        }
+    }
+    fn length(&self, name: &str, default: fn(Numeric) -> Length) -> Result<Option<Length>> {
+        self.quantity(name, default)
+    }
+    fn angle(&self, name: &str) -> Result<Option<Angle>> {
+        self.quantity(name, Angle::new::<degree>)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Format {
+    Text,
+    Csv,
+    Json,
+}
+
+// Output units, picked once up front - columns are made with them as types
+#[derive(Clone, Copy)]
+enum Units {
+    Imperial,
+    Metric,
+}
+#[derive(Clone, Copy)]
+enum Angles {
+    Moa,
+    Mil,
+}
+
+struct Output {
+    format: Format,
+    units: Units,
+    angles: Angles,
+}
+impl Output {
+    fn parse(args: &Args) -> Result<Self> {
+        let format = match args.text("format").unwrap_or("text") {
+            "text" => Format::Text,
+            "csv" => Format::Csv,
+            "json" => Format::Json,
+            other => return usage(format!("unknown format {:?}", other)),
+        };
+        let units = match args.text("units").unwrap_or("imperial") {
+            "imperial" => Units::Imperial,
+            "metric" => Units::Metric,
+            other => return usage(format!("unknown units {:?}", other)),
+        };
+        let angles = match args.text("angles").unwrap_or("moa") {
+            "moa" => Angles::Moa,
+            "mil" => Angles::Mil,
+            other => return usage(format!("unknown angles {:?}", other)),
+        };
+        Ok(Self {
+            format,
+            units,
+            angles,
+        })
+    }
+    // Distance, drop, velocity and energy units for the chosen system, then angles
+    fn columns<R>(&self, build: impl Fn(&dyn Picker<R>) -> Vec<Column<R>>) -> Vec<Column<R>>
+    where
+        R: 'static,
+    {
+        match (self.units, self.angles) {
+            (Units::Imperial, Angles::Moa) => {
+                build(&Pick::<yard, inch, foot_per_second, foot_pound, moa>::new())
+            }
+            (Units::Imperial, Angles::Mil) => {
+                build(&Pick::<yard, inch, foot_per_second, foot_pound, mil>::new())
+            }
+            (Units::Metric, Angles::Moa) => {
+                build(&Pick::<meter, centimeter, meter_per_second, joule, moa>::new())
+            }
+            (Units::Metric, Angles::Mil) => {
+                build(&Pick::<meter, centimeter, meter_per_second, joule, mil>::new())
+            }
+        }
+    }
+    fn write<R>(&self, metadata: &Metadata, columns: &[Column<R>], records: &[R]) -> Result<()> {
+        let out = io::stdout();
+        match self.format {
+            Format::Text => write_text(out.lock(), columns, records)?,
+            Format::Csv => write_csv(out.lock(), metadata, columns, records)?,
+            Format::Json => write_json(out.lock(), metadata, columns, records)?,
+        }
+        Ok(())
+    }
+}
+
+// Makes columns in whichever units were picked, without naming them at each use
+trait Picker<R> {
+    fn distance(&self, name: &'static str, value: fn(&R) -> Length) -> Column<R>;
+    fn drop(&self, name: &'static str, value: fn(&R) -> Length) -> Column<R>;
+    fn velocity(&self, name: &'static str, value: fn(&R) -> Velocity) -> Column<R>;
+    fn energy(&self, name: &'static str, value: fn(&R) -> Energy) -> Column<R>;
+    fn angle(&self, name: &'static str, value: fn(&R) -> Angle) -> Column<R>;
+}
+struct Pick<D, S, V, E, A>(PhantomData<(D, S, V, E, A)>);
+impl<D, S, V, E, A> Pick<D, S, V, E, A> {
+    fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+impl<R, D, S, V, E, A> Picker<R> for Pick<D, S, V, E, A>
+where
+    R: 'static,
+    D: length::Conversion<Numeric>,
+    S: length::Conversion<Numeric>,
+    V: velocity::Conversion<Numeric>,
+    E: energy::Conversion<Numeric>,
+    A: angle::Conversion<Numeric>,
+{
+    fn distance(&self, name: &'static str, value: fn(&R) -> Length) -> Column<R> {
+        Column::length::<D>(name, value)
+    }
+    fn drop(&self, name: &'static str, value: fn(&R) -> Length) -> Column<R> {
+        Column::length::<S>(name, value)
+    }
+    fn velocity(&self, name: &'static str, value: fn(&R) -> Velocity) -> Column<R> {
+        Column::velocity::<V>(name, value)
+    }
+    fn energy(&self, name: &'static str, value: fn(&R) -> Energy) -> Column<R> {
+        Column::energy::<E>(name, value)
+    }
+    fn angle(&self, name: &'static str, value: fn(&R) -> Angle) -> Column<R> {
+        Column::angle::<A>(name, value)
+    }
+}
+
+// Aligned columns, each as wide as its header
+fn write_text<W, R>(mut writer: W, columns: &[Column<R>], records: &[R]) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let headers = columns
+        .iter()
+        .map(|column| format!("{} ({})", column.name(), column.unit()))
+        .collect::<Vec<_>>();
+    let widths = headers
+        .iter()
+        .map(|header| header.chars().count().max(10))
+        .collect::<Vec<_>>();
+    let line = headers
+        .iter()
+        .zip(&widths)
+        .map(|(header, width)| format!("{:>width$}", header, width = width))
+        .collect::<Vec<_>>();
+    writeln!(writer, "{}", line.join("  "))?;
+    for record in records {
+        let line = columns
+            .iter()
+            .zip(&widths)
+            .map(|(column, width)| {
+                let precision = if column.unit() == "s" { 3 } else { 2 };
+                format!(
+                    "{:>width$.precision$}",
+                    column.value(record),
+                    width = width,
+                    precision = precision
+                )
+            })
+            .collect::<Vec<_>>();
+        writeln!(writer, "{}", line.join("  "))?;
+    }
+    Ok(())
+}
+
+fn main() {
+    let result = Args::parse().and_then(|args| {
+        let profile = match args.text("profile") {
+            Some(path) => Some(Profile::read(path)?),
+            None => None,
+        };
+        let drag = match args.text("drag") {
+            Some(drag) => drag,
+            None => profile.as_ref().map_or("G7", Stored::drag),
+        };
+        match drag {
+            "G1" => run::<G1>(&args, profile),
+            "G2" => run::<G2>(&args, profile),
+            "G5" => run::<G5>(&args, profile),
+            "G6" => run::<G6>(&args, profile),
+            "G7" => run::<G7>(&args, profile),
+            "G8" => run::<G8>(&args, profile),
+            "GI" => run::<GI>(&args, profile),
+            "GS" => run::<GS>(&args, profile),
+            other => usage(format!("unknown drag table {:?}", other)),
+        }
+    });
+    if let Err(err) = result {
+        match err.downcast_ref::<Usage>() {
+            Some(Usage(message)) if message.is_empty() => eprint!("{}", USAGE),
+            Some(Usage(message)) => eprint!("error: {}\n\n{}", message, USAGE),
+            None => eprintln!("error: {}", err),
+        }
+        process::exit(1);
+    }
+}
+
+fn run<T>(args: &Args, profile: Option<Profile>) -> Result<()>
+where
+    T: Projectile + From<ProjectileImpl> + DerefMut<Target = ProjectileImpl> + Clone,
+{
+    let output = Output::parse(args)?;
+    let tolerance = args
+        .length("tolerance", Length::new::<inch>)?
+        .unwrap_or_else(|| Length::new::<inch>(0.001));
+
+    // Rifle and load, from the profile if there is one, then flags on top
+    let mut builder = match &profile {
+        Some(profile) => profile.setup::<T>()?,
+        None => SimulationBuilder::<T>::new(),
+    };
+    if let Some(value) = args.length("caliber", Length::new::<inch>)? {
+        builder = builder.set_caliber(value)?;
+    }
+    if let Some(value) = args.quantity("weight", Mass::new::<grain>)? {
+        builder = builder.set_mass(value)?;
+    }
+    if let Some(value) = args.number("bc")? {
+        builder = builder.set_bc(value)?;
+    }
+    if let Some(value) = args.quantity("velocity", Velocity::new::<foot_per_second>)? {
+        builder = builder.set_velocity(value)?;
+    }
+    if let Some(value) = args.length("scope-height", Length::new::<inch>)? {
+        builder = builder.set_scope_height(value);
+    }
+
+    // Zero under standard conditions, unless the profile already has one for this rifle and load
+    // Its scope angles are stale once any flag above changes the ballistics
+    let zero = args.length("zero", Length::new::<yard>)?;
+    let stored = profile.as_ref().map(Stored::zero);
+    let changed = [
+        "drag",
+        "caliber",
+        "weight",
+        "bc",
+        "velocity",
+        "scope-height",
+    ]
+    .iter()
+    .any(|name| args.text(name).is_some());
+    let mut simulation = builder.init();
+    let (distance, pitch, yaw) = match (zero, stored) {
+        (
+            None,
+            Some(StoredZero {
+                distance,
+                angles: Some((pitch, yaw)),
+                ..
+            }),
+        ) if !changed => (distance, pitch, yaw),
+        (zero, stored) => {
+            let (distance, elevation, windage) = match (zero, stored) {
+                (Some(zero), _) => (zero, Length::new::<meter>(0.0), Length::new::<meter>(0.0)),
+                (None, Some(stored)) => (stored.distance, stored.elevation, stored.windage),
+                (None, None) => (
+                    Length::new::<yard>(100.0),
+                    Length::new::<meter>(0.0),
+                    Length::new::<meter>(0.0),
+                ),
+            };
+            let (pitch, yaw) =
+                simulation.find_zero_angles(distance, elevation, windage, tolerance)?;
+            (distance, pitch, yaw)
+        }
+    };
+
+    // Conditions for the shot itself
+    let mut conditions = simulation.conditions();
+    let temperature = args.quantity("temperature", ThermodynamicTemperature::new::<fahrenheit>)?;
+    if let Some(value) = temperature {
+        conditions = conditions.set_temperature(value)?;
+    }
+    if let Some(value) = args.quantity("pressure", Pressure::new::<inch_of_mercury>)? {
+        conditions = conditions.set_pressure(value)?;
+    }
+    if let Some(value) = args.number("humidity")? {
+        conditions = conditions.set_humidity(value)?;
+    }
+    if let Some(value) = args.quantity("wind-speed", Velocity::new::<mile_per_hour>)? {
+        conditions = conditions.set_wind_speed(value)?;
+    }
+    if let Some(value) = args.angle("wind-angle")? {
+        conditions = conditions.set_wind_angle(value)?;
+    }
+    if let Some(value) = args.angle("bearing")? {
+        conditions = conditions.set_bearing(value)?;
+    }
+    if let Some(value) = args.angle("latitude")? {
+        conditions = conditions.set_lattitude(value)?;
+    }
+    let mut builder = SimulationBuilder::from(simulation.with_conditions(conditions));
+    // Powder temperature moves the profile's measured velocity, unless --velocity replaced it
+    if let (Some(profile), Some(value), None) = (&profile, temperature, args.text("velocity")) {
+        builder = builder.set_velocity(profile.velocity_at(value))?;
+    }
+    let shot_angle = args
+        .angle("shot-angle")?
+        .unwrap_or_else(|| Angle::new::<radian>(0.0));
+    let cant = args
+        .angle("cant")?
+        .unwrap_or_else(|| Angle::new::<radian>(0.0));
+
+    match args.command.as_str() {
+        "zero" => {
+            let zero = Zeroed {
+                distance,
+                pitch,
+                yaw,
+            };
+            let columns = output.columns(|pick: &dyn Picker<Zeroed>| {
+                vec![
+                    pick.distance("distance", |z| z.distance),
+                    pick.angle("pitch", |z| z.pitch),
+                    pick.angle("yaw", |z| z.yaw),
+                ]
+            });
+            output.write(&simulation.metadata(), &columns, &[zero])
+        }
+        "card" => {
+            let shot = builder.set_shot_angle(shot_angle)?.set_cant(cant)?.init();
+            let start = args.length("start", Length::new::<yard>)?;
+            let end = args.length("end", Length::new::<yard>)?;
+            let step = args.length("step", Length::new::<yard>)?;
+            let rows = shot.range_card(
+                start.unwrap_or_else(|| Length::new::<yard>(0.0)),
+                end.unwrap_or_else(|| Length::new::<yard>(1000.0)),
+                step.unwrap_or_else(|| Length::new::<yard>(100.0)),
+                tolerance,
+            )?;
+            let columns = output.columns(|pick: &dyn Picker<Row>| {
+                vec![
+                    pick.distance("distance", |r| r.distance()),
+                    pick.drop("elevation", |r| r.elevation()),
+                    pick.drop("windage", |r| r.windage()),
+                    pick.velocity("velocity", |r| r.velocity()),
+                    pick.energy("energy", |r| r.energy()),
+                    Column::time::<second>("time", |r: &Row| r.time()),
+                    pick.angle("vertical", |r| r.vertical_angle()),
+                    pick.angle("horizontal", |r| r.horizontal_angle()),
+                ]
+            });
+            output.write(&shot.metadata(), &columns, &rows)
+        }
+        "solution" => {
+            let range = match args.length("range", Length::new::<yard>)? {
+                Some(range) => range,
+                None => return usage(String::from("solution needs --range")),
+            };
+            let shot = builder.init();
+            let solution = shot.firing_solution(Observation::new(range, shot_angle, cant))?;
+            let dial = shot.dial(solution.elevation(), solution.windage(), range)?;
+            let record = Shot {
+                range,
+                clicks: (dial.elevation().count(), dial.windage().count()),
+                solution,
+            };
+            let columns = output.columns(|pick: &dyn Picker<Shot>| {
+                vec![
+                    pick.distance("range", |s| s.range),
+                    pick.angle("elevation", |s| s.solution.elevation()),
+                    pick.angle("windage", |s| s.solution.windage()),
+                    Column::number("elevation", "clicks", |s: &Shot| s.clicks.0 as Numeric),
+                    Column::number("windage", "clicks", |s: &Shot| s.clicks.1 as Numeric),
+                    pick.velocity("velocity", |s| s.solution.sample().velocity()),
+                    pick.energy("energy", |s| s.solution.sample().energy()),
+                    Column::time::<second>("time", |s: &Shot| s.solution.sample().time()),
+                ]
+            });
+            output.write(&shot.metadata(), &columns, &[record])
+        }
+        other => usage(format!("unknown command {:?}", other)),
+    }
+}
+
+// Zero kept with a profile, with its scope angles once they have been found
+struct StoredZero {
+    distance: Length,
+    elevation: Length,
+    windage: Length,
+    angles: Option<(Angle, Angle)>,
+}
+
+// Everything a run needs from a profile
+// Without the profile feature there is never one to read, and --profile says so
+trait Stored: Sized {
+    fn read(path: &str) -> Result<Self>;
+    fn drag(&self) -> &'static str;
+    fn setup<T>(&self) -> Result<SimulationBuilder<T>>
+    where
+        T: Projectile + From<ProjectileImpl> + DerefMut<Target = ProjectileImpl>;
+    fn zero(&self) -> StoredZero;
+    fn velocity_at(&self, temperature: ThermodynamicTemperature) -> Velocity;
+}
+
+#[cfg(feature = "profile")]
+impl Stored for Profile {
+    fn read(path: &str) -> Result<Self> {
+        Ok(Profile::from_toml(&std::fs::read_to_string(path)?)?)
+    }
+    fn drag(&self) -> &'static str {
+        self.load.drag.name()
+    }
+    fn setup<T>(&self) -> Result<SimulationBuilder<T>>
+    where
+        T: Projectile + From<ProjectileImpl> + DerefMut<Target = ProjectileImpl>,
+    {
+        // Drag table may have been swapped with --drag, same as any other part of the load
+        let mut profile = self.clone();
+        profile.load.drag = T::DRAG_TABLE.parse()?;
+        Ok(profile.builder()?)
+    }
+    fn zero(&self) -> StoredZero {
+        let zero = &self.rifle.zero;
+        StoredZero {
+            distance: zero.distance,
+            elevation: zero.elevation,
+            windage: zero.windage,
+            angles: zero.pitch.zip(zero.yaw),
+        }
+    }
+    fn velocity_at(&self, temperature: ThermodynamicTemperature) -> Velocity {
+        self.load.velocity_at(temperature)
+    }
+}
+
+#[cfg(not(feature = "profile"))]
+enum Profile {}
+#[cfg(not(feature = "profile"))]
+impl Stored for Profile {
+    fn read(_: &str) -> Result<Self> {
+        usage(String::from(
+            "--profile needs the profile feature, ie, cargo install --features profile",
+        ))
+    }
+    fn drag(&self) -> &'static str {
+        match *self {}
+    }
+    fn setup<T>(&self) -> Result<SimulationBuilder<T>>
+    where
+        T: Projectile + From<ProjectileImpl> + DerefMut<Target = ProjectileImpl>,
+    {
+        match *self {}
+    }
+    fn zero(&self) -> StoredZero {
+        match *self {}
+    }
+    fn velocity_at(&self, _: ThermodynamicTemperature) -> Velocity {
+        match *self {}
+    }
+}
+
+struct Zeroed {
+    distance: Length,
+    pitch: Angle,
+    yaw: Angle,
+}
+struct Shot {
+    range: Length,
+    clicks: (i64, i64),
+    solution: Solution,
+}
@@ -0,0 +1,269 @@
+use crate::{
+    error::{Error, Result},
+    output::{Measurements, Packet},
+    projectiles::Projectile,
+    simulation::Simulation,
+    units::{
+        degree, meter, meter_per_second, mile_per_hour, moa, yard, Angle, Energy, Length, Time,
+        Velocity,
+    },
+    Numeric,
+};
+
+use std::{fmt, thread};
+
+// Single line of a range card, at an exact distance
+#[derive(Debug, Clone, Copy)]
+pub struct Row {
+    distance: Length,  // Distance along line of sight
+    elevation: Length, // Drop, relative to line of sight
+    windage: Length,   // Windage, relative to line of sight
+    velocity: Velocity,
+    energy: Energy,
+    time: Time,        // Time of flight
+    vertical: Angle,   // Elevation correction, from vertical_angle
+    horizontal: Angle, // Windage correction, from horizontal_angle
+}
+impl Row {
+    pub fn distance(&self) -> Length {
+        self.distance
+    }
+    pub fn elevation(&self) -> Length {
+        self.elevation
+    }
+    pub fn windage(&self) -> Length {
+        self.windage
+    }
+    pub fn velocity(&self) -> Velocity {
+        self.velocity
+    }
+    pub fn energy(&self) -> Energy {
+        self.energy
+    }
+    pub fn time(&self) -> Time {
+        self.time
+    }
+    pub fn vertical_angle(&self) -> Angle {
+        self.vertical
+    }
+    pub fn horizontal_angle(&self) -> Angle {
+        self.horizontal
+    }
+}
+impl<T> Packet<'_, T>
+where
+    T: Projectile,
+{
+    fn row(&self, tolerance: Length) -> Row {
+        Row {
+            distance: self.distance(),
+            elevation: self.elevation(),
+            windage: self.windage(),
+            velocity: self.velocity(),
+            energy: self.energy(),
+            time: self.time(),
+            vertical: self.vertical_angle(tolerance),
+            horizontal: self.horizontal_angle(tolerance),
+        }
+    }
+}
+
+impl<T> Simulation<T>
+where
+    T: Projectile,
+{
+    // Range card from start to end (inclusive) every step, all along line of sight
+    // Uses a single run, with each row interpolated exactly at its distance
+    // Ends early if the projectile never gets that far
+    pub fn range_card(
+        &self,
+        start: Length,
+        end: Length,
+        step: Length,
+        tolerance: Length,
+    ) -> Result<Vec<Row>> {
+        if start.is_sign_negative() {
+            return Err(Error::PositiveExpected(start.get::<meter>()));
+        } else if step <= Length::new::<meter>(0.0) {
+            return Err(Error::PositiveExpected(step.get::<meter>()));
+        }
+        let distances = (0..)
+            .map(|index| start + step * index as Numeric)
+            .take_while(|distance| *distance <= end);
+        Ok(self.rows(distances, end, tolerance))
+    }
+    // Rows at each of the distances, which must be ascending and no further than end
+    fn rows<I>(&self, distances: I, end: Length, tolerance: Length) -> Vec<Row>
+    where
+        I: Iterator<Item = Length>,
+    {
+        self.packets_at(distances, end)
+            .iter()
+            .map(|packet| packet.row(tolerance))
+            .collect()
+    }
+    // Packets at each of the distances, which must be ascending and no further than end
+    // Shared with plotting, anything past where the projectile reaches is left out
+    pub(crate) fn packets_at<I>(&self, distances: I, end: Length) -> Vec<Packet<'_, T>>
+    where
+        I: Iterator<Item = Length>,
+    {
+        let mut distances = distances.peekable();
+        let mut packets = Vec::new();
+        let mut prev: Option<Packet<T>> = None;
+
+        // Same early stop as zeroing, for ends past where the projectile can reach
+        let iter = self
+            .iter()
+            .max_distance(end)
+            .until(|p| p.plunging(Length::new::<meter>(0.0)));
+        for packet in iter {
+            while let Some(&distance) = distances.peek() {
+                if packet.distance() < distance {
+                    break;
+                }
+                let residual = |p: &Packet<T>| (p.distance() - distance).get::<meter>();
+                let found = match prev {
+                    Some(prev) => prev.crossing(&packet, &residual),
+                    None => packet,
+                };
+                packets.push(found);
+                distances.next();
+            }
+            prev = Some(packet);
+        }
+        packets
+    }
+}
+
+// Windage holds for a grid of wind speeds and clock directions, at each range
+// 3 o'clock is wind from the right, 12 o'clock a headwind
+#[derive(Debug, Clone)]
+pub struct WindCard {
+    ranges: Vec<Length>,
+    speeds: Vec<Velocity>,
+    clocks: Vec<u8>,
+    holds: Vec<Vec<Vec<Option<Angle>>>>, // Indexed by clock, then speed, then range
+}
+impl WindCard {
+    pub fn ranges(&self) -> &[Length] {
+        &self.ranges
+    }
+    pub fn speeds(&self) -> &[Velocity] {
+        &self.speeds
+    }
+    pub fn clocks(&self) -> &[u8] {
+        &self.clocks
+    }
+    // Windage hold, by index into clocks, speeds and ranges
+    // None if the projectile never reached that range
+    pub fn hold(&self, clock: usize, speed: usize, range: usize) -> Option<Angle> {
+        *self.holds.get(clock)?.get(speed)?.get(range)?
+    }
+}
+// One block per range, clock directions down and wind speeds across, holds in MOA
+impl fmt::Display for WindCard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (r, range) in self.ranges.iter().enumerate() {
+            writeln!(f, "{:.0} yd", range.get::<yard>())?;
+            write!(f, "clock")?;
+            for speed in &self.speeds {
+                write!(f, " | {:>3.0} mph", speed.get::<mile_per_hour>())?;
+            }
+            writeln!(f)?;
+            for (c, clock) in self.clocks.iter().enumerate() {
+                write!(f, "{:>5}", clock)?;
+                for s in 0..self.speeds.len() {
+                    match self.hold(c, s, r) {
+                        Some(hold) => write!(f, " | {:>7.2}", hold.get::<moa>())?,
+                        None => write!(f, " | {:>7}", "-")?,
+                    }
+                }
+                writeln!(f)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Simulation<T>
+where
+    T: Projectile + Clone + Send,
+{
+    // Wind card, with a run for every speed and clock direction, and conditions otherwise as they are
+    // Runs are split across available threads
+    pub fn wind_card(
+        &self,
+        ranges: &[Length],
+        speeds: &[Velocity],
+        clocks: &[u8],
+        tolerance: Length,
+    ) -> Result<WindCard> {
+        if clocks.iter().any(|clock| !(1..=12).contains(clock)) {
+            return Err(Error::OutOfRange {
+                min: 1.0,
+                max: 12.0,
+            });
+        } else if let Some(speed) = speeds.iter().find(|speed| speed.is_sign_negative()) {
+            return Err(Error::PositiveExpected(speed.get::<meter_per_second>()));
+        }
+        // Rows come out in order of distance, so run in that order and put holds back after
+        let mut order = (0..ranges.len()).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| {
+            ranges[a]
+                .get::<meter>()
+                .total_cmp(&ranges[b].get::<meter>())
+        });
+        let end = order
+            .last()
+            .map_or(Length::new::<meter>(0.0), |&index| ranges[index]);
+
+        let runs = clocks
+            .iter()
+            .flat_map(|&clock| speeds.iter().map(move |&speed| (clock, speed)))
+            .collect::<Vec<_>>();
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        let chunk = runs.len().div_ceil(threads).max(1);
+        let holds = thread::scope(|scope| {
+            let handles = runs
+                .chunks(chunk)
+                .map(|runs| {
+                    let mut simulation = self.clone();
+                    let order = &order;
+                    scope.spawn(move || {
+                        runs.iter()
+                            .map(|&(clock, speed)| {
+                                simulation.wind.velocity = speed;
+                                simulation.wind.yaw = Angle::new::<degree>(30.0 * clock as Numeric);
+                                let rows = simulation.rows(
+                                    order.iter().map(|&index| ranges[index]),
+                                    end,
+                                    tolerance,
+                                );
+                                let mut holds = vec![None; order.len()];
+                                for (row, &index) in rows.iter().zip(order) {
+                                    holds[index] = Some(row.horizontal_angle());
+                                }
+                                holds
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("wind card thread"))
+                .collect::<Vec<_>>()
+        });
+        Ok(WindCard {
+            ranges: ranges.to_vec(),
+            speeds: speeds.to_vec(),
+            clocks: clocks.to_vec(),
+            holds: holds
+                .chunks(speeds.len().max(1))
+                .map(|chunk| chunk.to_vec())
+                .collect(),
+        })
+    }
+}
@@ -1,6 +1,9 @@
-use crate::{units::Angle, Numeric};
+use crate::{
+    units::{Angle, Length},
+    Numeric,
+};
 
-use std::{error, fmt, result};
+use std::{error, fmt, io, result};
 
 pub type Result<T, E = Error> = result::Result<T, E>;
 
@@ -13,6 +16,7 @@ pub enum Error {
         min: Numeric,
         max: Numeric,
     },
+    GridShape,
     AngleRange {
         count: u64,
         pitch: Angle,
@@ -23,11 +27,29 @@ pub enum Error {
         pitch: Angle,
         yaw: Angle,
     },
-    AngleNotChanging {
+    OutOfReach {
+        distance: Length,
+    },
+    TurretTravel {
+        correction: Angle,
+        travel: Angle,
+    },
+    NoBracket {
         count: u64,
-        pitch: Angle,
-        yaw: Angle,
+        min: Numeric,
+        max: Numeric,
     },
+    IterationLimit {
+        count: u64,
+        value: Numeric,
+        residual: Numeric,
+    },
+    DragTable {
+        expected: &'static str,
+        found: String,
+    },
+    Io(io::Error),
+    Profile(String),
 }
 
 impl fmt::Display for Error {
@@ -41,6 +63,10 @@ impl fmt::Display for Error {
                 "Within Range Expected Error => min: {:#?} - {:#?}",
                 min, max
             ),
+            Self::GridShape => write!(
+                f,
+                "Grid Shape Error: rows must be non-empty and equal length"
+            ),
             Self::AngleRange { count, pitch, yaw } => write!(
                 f,
                 "{}: Outside Valid Range Error => pitch: {:#?}, yaw: {:#?}",
@@ -51,13 +77,53 @@ impl fmt::Display for Error {
                 "{}: Terminal Velocity Error => pitch: {:#?}, yaw: {:#?}",
                 count, pitch, yaw
             ),
-            Self::AngleNotChanging { count, pitch, yaw } => write!(
+            Self::OutOfReach { distance } => {
+                write!(f, "Out Of Reach Error => distance: {:#?}", distance)
+            }
+            Self::TurretTravel { correction, travel } => write!(
                 f,
-                "{}: Angle Not Changing Error => pitch: {:#?}, yaw: {:#?}",
-                count, pitch, yaw
+                "Turret Travel Error => correction: {:#?}, travel: {:#?}",
+                correction, travel
             ),
+            Self::NoBracket { count, min, max } => write!(
+                f,
+                "{}: No Sign Change Error => min: {:#?} - max: {:#?}",
+                count, min, max
+            ),
+            Self::IterationLimit {
+                count,
+                value,
+                residual,
+            } => write!(
+                f,
+                "{}: Iteration Limit Error => value: {:#?}, residual: {:#?}",
+                count, value, residual
+            ),
+            Self::DragTable {
+                expected,
+                ref found,
+            } => write!(
+                f,
+                "Drag Table Error => expected: {}, found: {}",
+                expected, found
+            ),
+            Self::Io(ref err) => write!(f, "Io Error: {}", err),
+            Self::Profile(ref err) => write!(f, "Profile Error: {}", err),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Self::Io(ref err) => Some(err),
+            _ => None,
         }
     }
 }
 
-impl error::Error for Error {}
+impl From<io::Error> for Error {
+    fn from(other: io::Error) -> Self {
+        Self::Io(other)
+    }
+}
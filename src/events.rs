@@ -0,0 +1,125 @@
+use crate::{
+    iter::{Iter, Termination},
+    output::{Measurements, Packet},
+    projectiles::Projectile,
+    units::{meter, meter_per_second, second, Length},
+    vectors::Vectors,
+    Numeric,
+};
+
+use std::{collections::VecDeque, iter::FusedIterator};
+
+// Points of interest relative to line of sight, each interpolated to where it happens
+#[derive(Debug)]
+pub enum Event<'t, T> {
+    NearZero(Packet<'t, T>), // Rising through line of sight
+    FarZero(Packet<'t, T>),  // Falling through line of sight
+    Apex(Packet<'t, T>),     // Maximum ordinate, highest point relative to line of sight
+}
+impl<T> Clone for Event<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for Event<'_, T> {}
+impl<'t, T> Event<'t, T>
+where
+    T: Projectile,
+{
+    pub fn packet(&self) -> &Packet<'t, T> {
+        match self {
+            Self::NearZero(packet) | Self::FarZero(packet) | Self::Apex(packet) => packet,
+        }
+    }
+    pub fn distance(&self) -> Length {
+        self.packet().distance()
+    }
+    pub fn elevation(&self) -> Length {
+        self.packet().elevation()
+    }
+}
+
+// Iterator adaptor yielding each event in order of time of flight
+// Every crossing is reported, so lobbed or downhill shots may see more than one of each
+#[derive(Debug)]
+pub struct Events<'t, T> {
+    iter: Iter<'t, T>,
+    prev: Option<Packet<'t, T>>,
+    pending: VecDeque<Event<'t, T>>, // Events found within the last step, more than one is possible
+}
+
+impl<'t, T> Iter<'t, T>
+where
+    T: Projectile,
+{
+    pub fn events(self) -> Events<'t, T> {
+        Events {
+            iter: self,
+            prev: None,
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+impl<T> Event<'_, T>
+where
+    T: Projectile,
+{
+    fn time(&self) -> Numeric {
+        self.packet().time().get::<second>()
+    }
+}
+
+impl<T> Events<'_, T>
+where
+    T: Projectile,
+{
+    // Reason the underlying iteration stopped, if it has
+    pub fn termination(&self) -> Option<Termination> {
+        self.iter.termination()
+    }
+}
+
+fn elevation<T>(packet: &Packet<T>) -> Numeric
+where
+    T: Projectile,
+{
+    packet.elevation().get::<meter>()
+}
+fn rising<T>(packet: &Packet<T>) -> Numeric
+where
+    T: Projectile,
+{
+    packet.relative_velocity().get_y().get::<meter_per_second>()
+}
+
+impl<'t, T> Iterator for Events<'t, T>
+where
+    T: Projectile,
+{
+    type Item = Event<'t, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            let packet = self.iter.next()?;
+            if let Some(prev) = self.prev.replace(packet) {
+                if elevation(&prev) < 0.0 && elevation(&packet) >= 0.0 {
+                    let found = prev.crossing(&packet, &elevation);
+                    self.pending.push_back(Event::NearZero(found));
+                }
+                if elevation(&prev) >= 0.0 && elevation(&packet) < 0.0 {
+                    let found = prev.crossing(&packet, &elevation);
+                    self.pending.push_back(Event::FarZero(found));
+                }
+                if rising(&prev) >= 0.0 && rising(&packet) < 0.0 {
+                    let found = prev.crossing(&packet, &rising);
+                    self.pending.push_back(Event::Apex(found));
+                }
+                self.pending
+                    .make_contiguous()
+                    .sort_by(|a, b| a.time().total_cmp(&b.time()));
+            }
+        }
+        self.pending.pop_front()
+    }
+}
+impl<'t, T> FusedIterator for Events<'t, T> where T: Projectile {}
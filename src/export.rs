@@ -0,0 +1,398 @@
+use crate::{
+    card::Row,
+    error::Result,
+    output::Measurements,
+    projectiles::Projectile,
+    simulation::{Simulation, Turret},
+    units::{
+        acceleration, angle, degree, energy, foot_per_second, foot_pound, inch, kelvin, kilogram,
+        length, meter, meter_per_second, meter_per_second_squared, moa, momentum, pascal, pound,
+        second, square_inch, time, velocity, yard, Acceleration, Angle, Area, Energy, Length,
+        Momentum, Time, Velocity,
+    },
+    Numeric,
+};
+
+use std::{borrow::Borrow, fmt, io::Write};
+
+use uom::si::Unit;
+
+// Single column of output, with values converted to the unit it was made with
+pub struct Column<R> {
+    name: &'static str,
+    unit: &'static str,
+    value: Box<dyn Fn(&R) -> Numeric>,
+}
+
+impl<R> fmt::Debug for Column<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Column")
+            .field("name", &self.name)
+            .field("unit", &self.unit)
+            .finish_non_exhaustive()
+    }
+}
+
+// Constructors for each kind of quantity, picking the unit as a type, ie, Column::length::<yard>
+macro_rules! columns {
+    ($($kind:ident => $quantity:ident,)+) => {
+        impl<R> Column<R> {
+            $(
+                pub fn $kind<U>(
+                    name: &'static str,
+                    value: impl Fn(&R) -> $quantity + 'static,
+                ) -> Self
+                where
+                    U: $kind::Conversion<Numeric>,
+                {
+                    Self {
+                        name,
+                        unit: U::abbreviation(),
+                        value: Box::new(move |record| value(record).get::<U>()),
+                    }
+                }
+            )+
+        }
+    };
+}
+columns! {
+    acceleration => Acceleration,
+    angle => Angle,
+    energy => Energy,
+    length => Length,
+    momentum => Momentum,
+    time => Time,
+    velocity => Velocity,
+}
+
+impl<R> Column<R> {
+    // Plain numbers, ie, turret clicks, labelled with whatever they count
+    pub fn number(
+        name: &'static str,
+        unit: &'static str,
+        value: impl Fn(&R) -> Numeric + 'static,
+    ) -> Self {
+        Self {
+            name,
+            unit,
+            value: Box::new(value),
+        }
+    }
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+    pub fn unit(&self) -> &'static str {
+        self.unit
+    }
+    pub fn value(&self, record: &R) -> Numeric {
+        (self.value)(record)
+    }
+}
+
+// Usual columns for packets or samples, in yards, inches, feet per second and MOA
+pub fn trajectory_columns<M>(tolerance: Length) -> Vec<Column<M>>
+where
+    M: Measurements,
+{
+    vec![
+        Column::time::<second>("time", |p: &M| p.time()),
+        Column::length::<yard>("distance", |p: &M| p.distance()),
+        Column::length::<inch>("elevation", |p: &M| p.elevation()),
+        Column::length::<inch>("windage", |p: &M| p.windage()),
+        Column::length::<inch>("height", |p: &M| p.height()),
+        Column::velocity::<foot_per_second>("velocity", |p: &M| p.velocity()),
+        Column::energy::<foot_pound>("energy", |p: &M| p.energy()),
+        Column::angle::<moa>("vertical", move |p: &M| p.vertical_angle(tolerance)),
+        Column::angle::<moa>("horizontal", move |p: &M| p.horizontal_angle(tolerance)),
+    ]
+}
+
+// Usual columns for range card rows, same units as trajectory_columns
+pub fn range_card_columns() -> Vec<Column<Row>> {
+    vec![
+        Column::length::<yard>("distance", |r: &Row| r.distance()),
+        Column::length::<inch>("elevation", |r: &Row| r.elevation()),
+        Column::length::<inch>("windage", |r: &Row| r.windage()),
+        Column::velocity::<foot_per_second>("velocity", |r: &Row| r.velocity()),
+        Column::energy::<foot_pound>("energy", |r: &Row| r.energy()),
+        Column::time::<second>("time", |r: &Row| r.time()),
+        Column::angle::<moa>("vertical", |r: &Row| r.vertical_angle()),
+        Column::angle::<moa>("horizontal", |r: &Row| r.horizontal_angle()),
+    ]
+}
+
+// Single simulation input, with its unit if it has one
+#[derive(Debug, Clone)]
+pub enum Value {
+    Number(Numeric),
+    Flag(bool),
+    Text(String),
+    Missing,
+}
+#[derive(Debug, Clone)]
+pub struct Entry {
+    name: &'static str,
+    value: Value,
+    unit: Option<&'static str>,
+}
+impl Entry {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+    pub fn unit(&self) -> Option<&'static str> {
+        self.unit
+    }
+}
+
+// Everything a simulation was set up with, written ahead of the data it produced
+#[derive(Debug, Clone)]
+pub struct Metadata {
+    entries: Vec<Entry>,
+}
+impl Metadata {
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+}
+
+macro_rules! entry {
+    ($name:expr, $quantity:expr, $unit:ty) => {
+        Entry {
+            name: $name,
+            value: Value::Number($quantity.get::<$unit>()),
+            unit: Some(<$unit>::abbreviation()),
+        }
+    };
+    ($name:expr, $value:expr) => {
+        Entry {
+            name: $name,
+            value: $value,
+            unit: None,
+        }
+    };
+}
+
+impl Turret {
+    fn entries(&self, names: [&'static str; 3]) -> [Entry; 3] {
+        [
+            entry!(names[0], self.click, degree),
+            entry!(names[1], Value::Number(self.scale)),
+            match self.travel {
+                Some(travel) => entry!(names[2], travel, degree),
+                None => entry!(names[2], Value::Missing),
+            },
+        ]
+    }
+}
+
+impl<T> Simulation<T>
+where
+    T: Projectile,
+{
+    // Inputs in SI units, apart from angles (degrees) and the projectile's bc (lb/in²)
+    pub fn metadata(&self) -> Metadata {
+        let bc = self.projectile.bc() * Area::new::<square_inch>(1.0);
+        let mut entries = vec![
+            entry!("projectile.model", Value::Text(T::DRAG_TABLE.to_string())),
+            entry!("projectile.caliber", self.projectile.radius() * 2.0, meter),
+            entry!("projectile.weight", self.projectile.mass(), kilogram),
+            entry!("projectile.bc", Value::Number(bc.get::<pound>())),
+            entry!(
+                "projectile.velocity",
+                self.projectile.velocity(),
+                meter_per_second
+            ),
+            entry!("scope.pitch", self.scope.pitch, degree),
+            entry!("scope.yaw", self.scope.yaw, degree),
+            entry!("scope.roll", self.scope.roll, degree),
+            entry!("scope.height", self.scope.height, meter),
+            entry!("scope.offset", self.scope.offset, meter),
+        ];
+        entries.extend(self.scope.elevation.entries([
+            "scope.elevation.click",
+            "scope.elevation.scale",
+            "scope.elevation.travel",
+        ]));
+        entries.extend(self.scope.windage.entries([
+            "scope.windage.click",
+            "scope.windage.scale",
+            "scope.windage.travel",
+        ]));
+        entries.extend([
+            entry!(
+                "atmosphere.temperature",
+                self.atmosphere.temperature,
+                kelvin
+            ),
+            entry!("atmosphere.pressure", self.atmosphere.pressure, pascal),
+            entry!(
+                "atmosphere.humidity",
+                Value::Number(self.atmosphere.humidity)
+            ),
+            entry!("wind.velocity", self.wind.velocity, meter_per_second),
+            entry!("wind.yaw", self.wind.yaw, degree),
+            entry!("wind.pitch", self.wind.pitch, degree),
+            entry!("shooter.pitch", self.shooter.pitch, degree),
+            entry!("shooter.yaw", self.shooter.yaw, degree),
+            entry!("shooter.roll", self.shooter.roll, degree),
+            entry!("shooter.lattitude", self.shooter.lattitude, degree),
+            entry!(
+                "shooter.gravity",
+                self.shooter.gravity,
+                meter_per_second_squared
+            ),
+            entry!("shooter.velocity", self.shooter.velocity, meter_per_second),
+            entry!("shooter.heading", self.shooter.heading, degree),
+            entry!("shooter.climb", self.shooter.climb, degree),
+            entry!("flags.coriolis", Value::Flag(self.flags.coriolis)),
+            entry!("flags.drag", Value::Flag(self.flags.drag)),
+            entry!("flags.gravity", Value::Flag(self.flags.gravity)),
+            entry!("time_step", self.time_step, second),
+        ]);
+        Metadata { entries }
+    }
+}
+
+// Quoted only when needed, doubling any quotes inside
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// Metadata as '#' comment lines (name,value,unit), then a header of "name (unit)", then one line
+// per record - most readers can skip the comments, ie, pandas' comment='#'
+pub fn write_csv<W, R, I>(
+    mut writer: W,
+    metadata: &Metadata,
+    columns: &[Column<R>],
+    records: I,
+) -> Result<()>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: Borrow<R>,
+{
+    for entry in metadata.entries() {
+        let value = match entry.value() {
+            Value::Number(value) => value.to_string(),
+            Value::Flag(value) => value.to_string(),
+            Value::Text(value) => csv_field(value),
+            Value::Missing => String::new(),
+        };
+        writeln!(
+            writer,
+            "# {},{},{}",
+            entry.name(),
+            value,
+            csv_field(entry.unit().unwrap_or_default())
+        )?;
+    }
+    let header = columns
+        .iter()
+        .map(|column| csv_field(&format!("{} ({})", column.name(), column.unit())))
+        .collect::<Vec<_>>();
+    writeln!(writer, "{}", header.join(","))?;
+    for record in records {
+        let line = columns
+            .iter()
+            .map(|column| column.value(record.borrow()).to_string())
+            .collect::<Vec<_>>();
+        writeln!(writer, "{}", line.join(","))?;
+    }
+    Ok(())
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// JSON has no NaN or infinity, so those are written as null
+fn json_number(value: Numeric) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        String::from("null")
+    }
+}
+
+// Single object, with metadata keyed by name, column names and units, then rows as arrays of
+// values in column order
+pub fn write_json<W, R, I>(
+    mut writer: W,
+    metadata: &Metadata,
+    columns: &[Column<R>],
+    records: I,
+) -> Result<()>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: Borrow<R>,
+{
+    writeln!(writer, "{{")?;
+    writeln!(writer, "  \"metadata\": {{")?;
+    for (index, entry) in metadata.entries().iter().enumerate() {
+        let value = match entry.value() {
+            Value::Number(value) => json_number(*value),
+            Value::Flag(value) => value.to_string(),
+            Value::Text(value) => json_string(value),
+            Value::Missing => String::from("null"),
+        };
+        let unit = entry.unit().map_or(String::from("null"), json_string);
+        let comma = if index + 1 < metadata.entries().len() {
+            ","
+        } else {
+            ""
+        };
+        writeln!(
+            writer,
+            "    {}: {{ \"value\": {}, \"unit\": {} }}{}",
+            json_string(entry.name()),
+            value,
+            unit,
+            comma
+        )?;
+    }
+    writeln!(writer, "  }},")?;
+    let header = columns
+        .iter()
+        .map(|column| {
+            format!(
+                "{{ \"name\": {}, \"unit\": {} }}",
+                json_string(column.name()),
+                json_string(column.unit())
+            )
+        })
+        .collect::<Vec<_>>();
+    writeln!(writer, "  \"columns\": [{}],", header.join(", "))?;
+    write!(writer, "  \"rows\": [")?;
+    for (index, record) in records.into_iter().enumerate() {
+        let line = columns
+            .iter()
+            .map(|column| json_number(column.value(record.borrow())))
+            .collect::<Vec<_>>();
+        let comma = if index > 0 { "," } else { "" };
+        write!(writer, "{}\n    [{}]", comma, line.join(", "))?;
+    }
+    writeln!(writer, "\n  ]")?;
+    writeln!(writer, "}}")?;
+    Ok(())
+}
@@ -10,7 +10,7 @@ use ordered_float::OrderedFloat as OrdF;
 
 // Entry
 #[derive(Debug)]
-pub struct Entry<'a, V>(btree_map::Entry<'a, OrdF<Numeric>, V>);
+pub struct Entry<'a, V>(#[allow(dead_code)] btree_map::Entry<'a, OrdF<Numeric>, V>);
 
 // Range
 #[derive(Debug, Clone)]
@@ -130,9 +130,7 @@ impl<V> IntoIterator for FloatMap<V> {
     type IntoIter = FloatIntoIter<V>;
     type Item = <Self::IntoIter as Iterator>::Item;
     fn into_iter(self) -> Self::IntoIter {
-        Self::IntoIter {
-            0: self.0.into_iter(),
-        }
+        FloatIntoIter(self.0.into_iter())
     }
 }
 
@@ -206,15 +204,13 @@ impl<V> FromIterator<<Self as IntoIterator>::Item> for FloatMap<V> {
     where
         I: IntoIterator<Item = <Self as IntoIterator>::Item>,
     {
-        Self {
-            0: iter.into_iter().map(wrap_own).collect::<_>(),
-        }
+        Self(iter.into_iter().map(wrap_own).collect::<_>())
     }
 }
 
 impl<V> FloatMap<V> {
     pub fn new() -> Self {
-        Self { 0: BTreeMap::new() }
+        Self(BTreeMap::new())
     }
     pub fn clear(&mut self) {
         self.0.clear()
@@ -241,12 +237,10 @@ impl<V> FloatMap<V> {
         self.0.append(&mut other.0);
     }
     pub fn iter(&self) -> Iter<'_, V> {
-        Iter { 0: self.0.iter() }
+        Iter(self.0.iter())
     }
     pub fn iter_mut(&mut self) -> IterMut<'_, V> {
-        IterMut {
-            0: self.0.iter_mut(),
-        }
+        IterMut(self.0.iter_mut())
     }
     pub fn range<R>(&self, range: R) -> Range<'_, V>
     where
@@ -255,9 +249,7 @@ impl<V> FloatMap<V> {
         let start = wrap_bound(range.start_bound());
         let end = wrap_bound(range.end_bound());
 
-        Range {
-            0: self.0.range((start, end)),
-        }
+        Range(self.0.range((start, end)))
     }
     pub fn range_mut<R>(&mut self, range: R) -> RangeMut<'_, V>
     where
@@ -266,19 +258,13 @@ impl<V> FloatMap<V> {
         let start = wrap_bound(range.start_bound());
         let end = wrap_bound(range.end_bound());
 
-        RangeMut {
-            0: self.0.range_mut((start, end)),
-        }
+        RangeMut(self.0.range_mut((start, end)))
     }
-    pub fn entry(&mut self, k: Numeric) -> Entry<V> {
-        Entry {
-            0: self.0.entry(OrdF(k)),
-        }
+    pub fn entry(&mut self, k: Numeric) -> Entry<'_, V> {
+        Entry(self.0.entry(OrdF(k)))
     }
     pub fn split_off(&mut self, k: Numeric) -> Self {
-        Self {
-            0: self.0.split_off(&OrdF(k)),
-        }
+        Self(self.0.split_off(&OrdF(k)))
     }
     pub fn len(&self) -> usize {
         self.0.len()
@@ -287,15 +273,13 @@ impl<V> FloatMap<V> {
         self.0.is_empty()
     }
     pub fn keys(&self) -> Keys<'_, V> {
-        Keys { 0: self.0.keys() }
+        Keys(self.0.keys())
     }
     pub fn values(&self) -> Values<'_, V> {
-        Values { 0: self.0.values() }
+        Values(self.0.values())
     }
     pub fn values_mut(&mut self) -> ValuesMut<'_, V> {
-        ValuesMut {
-            0: self.0.values_mut(),
-        }
+        ValuesMut(self.0.values_mut())
     }
 }
 fn wrap_bound(bound: Bound<&Numeric>) -> Bound<OrdF<Numeric>> {
@@ -305,11 +289,11 @@ fn wrap_bound(bound: Bound<&Numeric>) -> Bound<OrdF<Numeric>> {
         Bound::Included(f) => Bound::Included(OrdF(*f)),
     }
 }
-fn unwrap_mut<'k, 'v, V>(kv: (&'k OrdF<Numeric>, &'v mut V)) -> (Numeric, &'v mut V) {
+fn unwrap_mut<'v, V>(kv: (&OrdF<Numeric>, &'v mut V)) -> (Numeric, &'v mut V) {
     let (&OrdF(k), v) = kv;
     (k, v)
 }
-fn unwrap_ref<'k, 'v, V>(kv: (&'k OrdF<Numeric>, &'v V)) -> (Numeric, &'v V) {
+fn unwrap_ref<'v, V>(kv: (&OrdF<Numeric>, &'v V)) -> (Numeric, &'v V) {
     let (&OrdF(k), v) = kv;
     (k, v)
 }
@@ -0,0 +1,188 @@
+use crate::{
+    error::{Error, Result},
+    iter::{Iter, Termination},
+    output::{Measurements, Packet},
+    projectiles::Projectile,
+    simulation::Simulation,
+    units::{meter, second, Length, Time},
+    Numeric,
+};
+
+use std::iter::FusedIterator;
+
+// Number of bisections used to locate a crossing between two steps
+// Each halves the bracket, so this is enough to exhaust the precision of the fraction
+const ITERATIONS: u32 = 52;
+
+impl<'t, T> Packet<'t, T>
+where
+    T: Projectile,
+{
+    // Cubic Hermite interpolation between this packet and the following one
+    // Position and velocity are both known at each end, so the interpolant matches
+    // the slope at each step - this is far closer than linear interpolation for large time steps
+    // Velocity is the derivative of the same polynomial, so both remain consistent
+    pub fn interpolate(&self, other: &Self, fraction: Numeric) -> Self {
+        let s = fraction;
+        let (s2, s3) = (s * s, s * s * s);
+        let h = other.time - self.time;
+
+        let h00 = 2.0 * s3 - 3.0 * s2 + 1.0;
+        let h10 = s3 - 2.0 * s2 + s;
+        let h01 = -2.0 * s3 + 3.0 * s2;
+        let h11 = s3 - s2;
+
+        let d00 = 6.0 * s2 - 6.0 * s;
+        let d10 = 3.0 * s2 - 4.0 * s + 1.0;
+        let d01 = -6.0 * s2 + 6.0 * s;
+        let d11 = 3.0 * s2 - 2.0 * s;
+
+        Self {
+            simulation: self.simulation,
+            time: self.time + h * s,
+            position: self.position * h00
+                + self.velocity * h * h10
+                + other.position * h01
+                + other.velocity * h * h11,
+            velocity: (self.position * d00 + other.position * d01) / h
+                + self.velocity * d10
+                + other.velocity * d11,
+        }
+    }
+    // Find where the residual crosses zero between this packet and the following one
+    // Assumes the residual changes sign across the step, which callers check beforehand
+    pub(crate) fn crossing<F>(&self, other: &Self, residual: &F) -> Self
+    where
+        F: Fn(&Packet<T>) -> Numeric,
+    {
+        let below = residual(self) < 0.0;
+        let (mut lo, mut hi) = (0.0, 1.0);
+        for _ in 0..ITERATIONS {
+            let mid = (lo + hi) / 2.0;
+            if (residual(&self.interpolate(other, mid)) < 0.0) == below {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        self.interpolate(other, hi)
+    }
+}
+
+impl<T> Simulation<T>
+where
+    T: Projectile,
+{
+    // First crossing of the residual, for a target at about the given distance
+    // Same early stop as zeroing - once below and falling steeply, it is never getting there
+    pub(crate) fn reach<F>(&self, distance: Length, residual: F) -> Result<Packet<'_, T>>
+    where
+        F: Fn(&Packet<T>) -> Numeric,
+    {
+        self.iter()
+            .until(|p| p.plunging(Length::new::<meter>(0.0)))
+            .crossings(residual)
+            .next()
+            .ok_or(Error::OutOfReach { distance })
+    }
+}
+
+// Iterator adaptor yielding a packet interpolated exactly where the residual crosses zero
+// Crossings are detected as a change of sign between consecutive steps, in either direction
+#[derive(Debug)]
+pub struct Crossings<'t, T, F> {
+    iter: Iter<'t, T>,
+    residual: F,
+    prev: Option<Packet<'t, T>>,
+}
+
+impl<'t, T> Iter<'t, T>
+where
+    T: Projectile,
+{
+    pub fn crossings<F>(self, residual: F) -> Crossings<'t, T, F>
+    where
+        F: Fn(&Packet<T>) -> Numeric,
+    {
+        Crossings {
+            iter: self,
+            residual,
+            prev: None,
+        }
+    }
+}
+
+impl<T, F> Crossings<'_, T, F>
+where
+    T: Projectile,
+{
+    // Reason the underlying iteration stopped, if it has
+    pub fn termination(&self) -> Option<Termination> {
+        self.iter.termination()
+    }
+}
+
+impl<'t, T, F> Iterator for Crossings<'t, T, F>
+where
+    T: Projectile,
+    F: Fn(&Packet<T>) -> Numeric,
+{
+    type Item = Packet<'t, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        // Very first packet may sit exactly on the crossing, with nothing before it to bracket
+        let mut prev = match self.prev.take() {
+            Some(prev) => prev,
+            None => {
+                let first = self.iter.next()?;
+                if (self.residual)(&first) == 0.0 {
+                    self.prev = Some(first);
+                    return Some(first);
+                }
+                first
+            }
+        };
+        let mut below = (self.residual)(&prev) < 0.0;
+        for packet in &mut self.iter {
+            let next_below = (self.residual)(&packet) < 0.0;
+            if below != next_below {
+                let found = prev.crossing(&packet, &self.residual);
+                self.prev = Some(packet);
+                return Some(found);
+            }
+            prev = packet;
+            below = next_below;
+        }
+        None
+    }
+}
+impl<'t, T, F> FusedIterator for Crossings<'t, T, F>
+where
+    T: Projectile,
+    F: Fn(&Packet<T>) -> Numeric,
+{
+}
+
+impl<T> Simulation<T>
+where
+    T: Projectile,
+{
+    // First state at the given distance along line of sight, interpolated between steps
+    pub fn packet_at_distance(&self, distance: Length) -> Option<Packet<'_, T>> {
+        self.iter()
+            .crossings(|p: &Packet<T>| (p.distance() - distance).get::<meter>())
+            .next()
+    }
+    // State at the given time of flight, interpolated between steps
+    pub fn packet_at_time(&self, time: Time) -> Option<Packet<'_, T>> {
+        self.iter()
+            .crossings(|p: &Packet<T>| (p.time() - time).get::<second>())
+            .next()
+    }
+    // First state where elevation relative to line of sight crosses the given value
+    // Trajectory usually crosses twice (rising, then falling) - use Iter::crossings for each
+    pub fn packet_at_elevation(&self, elevation: Length) -> Option<Packet<'_, T>> {
+        self.iter()
+            .crossings(|p: &Packet<T>| (p.elevation() - elevation).get::<meter>())
+            .next()
+    }
+}
@@ -1,25 +1,68 @@
 use crate::{
-    output::Packet,
+    output::{Measurements, Packet},
     projectiles::Projectile,
     simulation::Simulation,
     units::{
-        acceleration, length, meter, meter_per_second, meter_per_second_squared, second,
+        acceleration, length, meter, meter_per_second, meter_per_second_squared, ratio, second,
         typenum::P2, velocity, Acceleration, Length, Time, Velocity,
     },
     vectors::{MyVector3, Vectors},
+    Numeric,
 };
 
-use std::iter::FusedIterator;
+use std::{fmt, iter::FusedIterator};
+
+type Predicate<'t, T> = Box<dyn Fn(&Packet<'t, T>) -> bool + 't>;
 
 // Iterator over PointMassModel, steps through time and adjust position and velocity vectors
 // Has reference to current simulation model for calculations
 // Item lifetime also timed to this lifetime
-#[derive(Debug)]
 pub struct Iter<'t, T> {
-    simulation: &'t Simulation<T>, // Reference to model used for calculations
-    position: MyVector3<length::Dimension>, // Position (m)
+    pub(crate) simulation: &'t Simulation<T>, // Reference to model used for calculations
+    position: MyVector3<length::Dimension>,   // Position (m)
     velocity: MyVector3<velocity::Dimension>, // Velocity (m/s)
-    time: Time,                    // Position in time (s)
+    time: Time,                               // Position in time (s)
+
+    max_time: Option<Time>,         // Stop once time of flight reaches this
+    max_distance: Option<Length>,   // Stop once distance along line of sight reaches this
+    min_velocity: Option<Velocity>, // Stop once velocity drops to this
+    min_mach: Option<Numeric>,      // Stop once mach drops to this
+    min_height: Option<Length>,     // Stop once height (level with horizon) drops to this
+    predicate: Option<Predicate<'t, T>>, // Stop once caller defined condition holds
+    termination: Option<Termination>, // Reason iteration stopped, None while still running
+}
+
+// Condition which ended iteration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Termination {
+    Stalled,
+    Time,
+    Distance,
+    Velocity,
+    Mach,
+    Height,
+    Predicate,
+}
+
+impl<T> fmt::Debug for Iter<'_, T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Iter")
+            .field("simulation", &self.simulation)
+            .field("position", &self.position)
+            .field("velocity", &self.velocity)
+            .field("time", &self.time)
+            .field("max_time", &self.max_time)
+            .field("max_distance", &self.max_distance)
+            .field("min_velocity", &self.min_velocity)
+            .field("min_mach", &self.min_mach)
+            .field("min_height", &self.min_height)
+            .field("predicate", &self.predicate.is_some())
+            .field("termination", &self.termination)
+            .finish()
+    }
 }
 impl<T> Simulation<T>
 where
@@ -33,21 +76,30 @@ where
             position,
             velocity,
             time: Time::new::<second>(0.0),
+
+            max_time: None,
+            max_distance: None,
+            min_velocity: None,
+            min_mach: None,
+            min_height: None,
+            predicate: None,
+            termination: None,
         }
     }
     // Rotated velocity vector, accounts for muzzle/shooter pitch, and yaw (bearing)
-    // Start with velocity value along X unit vector
+    // Start with velocity value along X unit vector, then carry along any platform velocity
     fn absolute_projectile_velocity(&self) -> MyVector3<velocity::Dimension> {
-        MyVector3::new(
-            self.projectile.velocity(),
-            Velocity::new::<meter_per_second>(0.0),
-            Velocity::new::<meter_per_second>(0.0),
-        )
-        .pivot_y(self.scope.yaw())
-        .pivot_z(self.scope.pitch())
-        .pivot_x(self.shooter.roll())
-        .pivot_z(self.shooter.pitch())
-        .pivot_y(self.shooter.yaw())
+        self.shooter.velocity()
+            + MyVector3::new(
+                self.projectile.velocity(),
+                Velocity::new::<meter_per_second>(0.0),
+                Velocity::new::<meter_per_second>(0.0),
+            )
+            .pivot_y(self.scope.yaw())
+            .pivot_z(self.scope.pitch())
+            .pivot_x(self.shooter.roll())
+            .pivot_z(self.shooter.pitch())
+            .pivot_y(self.shooter.yaw())
     }
     // Projectiles position relative to scope
     fn absolute_projectile_position(&self) -> MyVector3<length::Dimension> {
@@ -62,6 +114,74 @@ where
         .pivot_y(self.shooter.yaw())
     }
 }
+// Stop conditions - the packet meeting a condition is still yielded, so that it can be used
+// to bracket the crossing, then iteration ends and reports which condition was met
+impl<'t, T> Iter<'t, T>
+where
+    T: Projectile,
+{
+    pub fn max_time(mut self, value: Time) -> Self {
+        self.max_time = Some(value);
+        self
+    }
+    pub fn max_distance(mut self, value: Length) -> Self {
+        self.max_distance = Some(value);
+        self
+    }
+    pub fn min_velocity(mut self, value: Velocity) -> Self {
+        self.min_velocity = Some(value);
+        self
+    }
+    pub fn min_mach(mut self, value: Numeric) -> Self {
+        self.min_mach = Some(value);
+        self
+    }
+    pub fn min_height(mut self, value: Length) -> Self {
+        self.min_height = Some(value);
+        self
+    }
+    pub fn until<P>(mut self, predicate: P) -> Self
+    where
+        P: Fn(&Packet<'t, T>) -> bool + 't,
+    {
+        self.predicate = Some(Box::new(predicate));
+        self
+    }
+    pub fn termination(&self) -> Option<Termination> {
+        self.termination
+    }
+    // First condition met by this packet, if any
+    fn terminates(&self, packet: &Packet<'t, T>) -> Option<Termination> {
+        if self.max_time.is_some_and(|max| packet.time() >= max) {
+            Some(Termination::Time)
+        } else if self
+            .max_distance
+            .is_some_and(|max| packet.distance() >= max)
+        {
+            Some(Termination::Distance)
+        } else if self
+            .min_velocity
+            .is_some_and(|min| packet.velocity() <= min)
+        {
+            Some(Termination::Velocity)
+        } else if self
+            .min_mach
+            .is_some_and(|min| self.simulation.mach(packet.velocity).get::<ratio::ratio>() <= min)
+        {
+            Some(Termination::Mach)
+        } else if self.min_height.is_some_and(|min| packet.height() <= min) {
+            Some(Termination::Height)
+        } else if self
+            .predicate
+            .as_ref()
+            .is_some_and(|predicate| predicate(packet))
+        {
+            Some(Termination::Predicate)
+        } else {
+            None
+        }
+    }
+}
 // Create an new iterator over Simulation
 impl<'t, T> IntoIterator for &'t Simulation<T>
 where
@@ -78,10 +198,15 @@ where
 // Contains time, position, and velocity of projectile, and reference to simulation used
 impl<'t, T> Iterator for Iter<'t, T>
 where
-    Self: Newtonian,
+    T: Projectile,
 {
     type Item = Packet<'t, T>;
     fn next(&mut self) -> Option<Self::Item> {
+        // Once stopped, stay stopped
+        if self.termination.is_some() {
+            return None;
+        }
+
         // Previous values captured to be returned, so that time 0 can be accounted for
         let &mut Self {
             time,
@@ -101,17 +226,18 @@ where
         // that are still moving forward, just at different angles
         //
         // This position check is still bad, however, as position may take a few ticks to change.
-        // For practical purposes, this still may suffice.  I want to take this check out eventually, and
-        // somehow allow caller to decide when to halt, ie, through filtering adaptors, although am not sure
-        // how to check previous iteration values in standard iterator adaptors.
+        // It is kept as a last resort, callers should prefer the stop conditions above to decide when to halt
         if self.position.get_x() != position.get_x() {
-            Some(Self::Item {
-                simulation: &self.simulation,
+            let packet = Self::Item {
+                simulation: self.simulation,
                 time,
                 position,
                 velocity,
-            })
+            };
+            self.termination = self.terminates(&packet);
+            Some(packet)
         } else {
+            self.termination = Some(Termination::Stalled);
             None
         }
     }
@@ -157,9 +283,7 @@ where
         &self,
         velocity: MyVector3<velocity::Dimension>,
     ) -> MyVector3<acceleration::Dimension> {
-        self.simulation.coriolis_acceleration(velocity)
-            + self.simulation.drag_acceleration(velocity)
-            + self.simulation.gravity_acceleration()
+        self.simulation.acceleration(velocity)
     }
     fn delta_time(&self) -> Time {
         self.simulation.time_step
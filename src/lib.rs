@@ -1,21 +1,44 @@
 pub type Numeric = f64;
 pub type NumericMap = float_map::FloatMap<Numeric>;
 
+#[macro_use]
+extern crate uom;
+
 #[macro_use]
 mod float_map;
 mod consts;
 mod physics;
+#[cfg(feature = "serde")]
+mod serialize;
 mod vectors;
 
+pub mod card;
 pub mod error;
+pub mod events;
+pub mod export;
+pub mod interpolate;
 pub mod iter;
 pub mod output;
+pub mod plot;
+#[cfg(feature = "profile")]
+pub mod profile;
 #[allow(clippy::approx_constant)]
 pub mod projectiles;
+pub mod reticle;
 pub mod simulation;
+pub mod terrain;
+pub mod trajectory;
+pub mod turret;
 pub mod units;
 pub mod solvers {
-    pub use self::zero::*;
+    pub use self::{lead::*, mpbr::*, range::*, solution::*, wind::*, zero::*};
+    pub mod lead;
+    pub mod mpbr;
+    #[allow(clippy::float_cmp)]
+    pub mod range;
+    mod roots;
+    pub mod solution;
+    pub mod wind;
     #[allow(clippy::float_cmp)]
     #[allow(clippy::nonminimal_bool)]
     pub mod zero;
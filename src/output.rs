@@ -1,10 +1,19 @@
 use crate::{
     projectiles::Projectile,
     simulation::Simulation,
-    units::{length, meter, typenum::P2, velocity, Angle, Energy, Length, Time, Velocity},
+    units::{
+        length, meter, ratio, typenum::P2, velocity, Acceleration, Angle, Energy, Length, Momentum,
+        Time, Velocity,
+    },
     vectors::{MyVector3, Norm, Vectors},
+    Numeric,
 };
 
+#[cfg(feature = "serde")]
+use crate::serialize::quantity;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 // Output of iteration, need a better name to encapsulate a moving projectile
 #[derive(Debug)]
 pub struct Packet<'t, T> {
@@ -13,6 +22,82 @@ pub struct Packet<'t, T> {
     pub(crate) position: MyVector3<length::Dimension>, // Position (m)
     pub(crate) velocity: MyVector3<velocity::Dimension>, // Velocity (m/s)
 }
+impl<T> Clone for Packet<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for Packet<'_, T> {}
+impl<T> Packet<'_, T> {
+    // Velocity rotated back to line of sight, same as relative_position()
+    // Line of sight moves with the platform, so its velocity is taken off first
+    pub(crate) fn relative_velocity(&self) -> MyVector3<velocity::Dimension> {
+        (self.velocity - self.simulation.shooter.velocity())
+            .pivot_y(-self.simulation.shooter.yaw())
+            .pivot_z(-self.simulation.shooter.pitch())
+            .pivot_x(-self.simulation.shooter.roll())
+    }
+}
+
+// Owned copy of a packet, with anything needing the simulation worked out up front
+// Not tied to the simulation's lifetime, so can be stored, cached, or sent across threads
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct Sample {
+    #[cfg_attr(feature = "serde", serde(with = "quantity"))]
+    time: Time, // Position in time (s)
+    position: MyVector3<length::Dimension>,   // Position (m)
+    velocity: MyVector3<velocity::Dimension>, // Velocity (m/s)
+    relative_position: MyVector3<length::Dimension>, // Position relative to line of sight (m)
+    #[cfg_attr(feature = "serde", serde(with = "quantity"))]
+    energy: Energy, // Kinetic energy (J)
+    mach: Numeric,                            // Airspeed relative to speed of sound
+    cd: Numeric,                              // Coefficient of drag, scaled by form factor
+    #[cfg_attr(feature = "serde", serde(with = "quantity"))]
+    drag: Acceleration, // Drag deceleration (m/s²)
+    #[cfg_attr(feature = "serde", serde(with = "quantity"))]
+    acceleration: Acceleration, // Total acceleration (m/s²)
+    #[cfg_attr(feature = "serde", serde(with = "quantity"))]
+    momentum: Momentum, // Momentum (kg·m/s)
+    #[cfg_attr(feature = "serde", serde(with = "quantity"))]
+    speed_of_sound: Velocity, // Speed of sound (m/s)
+}
+impl<T> From<Packet<'_, T>> for Sample
+where
+    T: Projectile,
+{
+    fn from(other: Packet<'_, T>) -> Self {
+        Self {
+            time: other.time,
+            position: other.position,
+            velocity: other.velocity,
+            relative_position: other.relative_position(),
+            energy: other.energy(),
+            mach: other.mach(),
+            cd: other.cd(),
+            drag: other.drag(),
+            acceleration: other.acceleration(),
+            momentum: other.momentum(),
+            speed_of_sound: other.speed_of_sound(),
+        }
+    }
+}
+
+impl<T> Packet<'_, T>
+where
+    T: Projectile,
+{
+    // Below the given elevation and falling steeper than 45 degrees, relative to line of sight
+    // Projectile can never come back up from here, so nothing further along is worth waiting for
+    pub(crate) fn plunging(&self, elevation: Length) -> bool {
+        let velocity = self.relative_velocity();
+        self.elevation() < elevation && -velocity.get_y() > velocity.get_x().abs()
+    }
+}
 
 impl<T> Measurements for Packet<'_, T>
 where
@@ -27,6 +112,93 @@ where
     fn energy(&self) -> Energy {
         self.velocity.norm().powi(P2::new()) * self.simulation.projectile.mass() * 0.5
     }
+    // Height relative to the scope, level with the horizon rather than line of sight
+    fn height(&self) -> Length {
+        self.position.get_y()
+    }
+    // During the simulation, the velocity of the projectile is rotated to allign with
+    // the shooter's bearing (azimuth and line of sight)
+    // This function returns the position rotated back to the initial frame of reference
+    // This is used during zero'ing and is output in the drop table
+    // Line of sight moves with the platform, so holds are from where it has got to by now
+    fn relative_position(&self) -> MyVector3<length::Dimension> {
+        (self.position - self.simulation.shooter.velocity() * self.time)
+            .pivot_y(-self.simulation.shooter.yaw())
+            .pivot_z(-self.simulation.shooter.pitch())
+            .pivot_x(-self.simulation.shooter.roll())
+    }
+    // Same mach the drag table is looked up with, and min_mach stops on - through the air, not ground
+    fn mach(&self) -> Numeric {
+        self.simulation.mach(self.velocity).get::<ratio::ratio>()
+    }
+    // Drag table's coefficient at this mach, scaled by the projectile's form factor
+    fn cd(&self) -> Numeric {
+        self.simulation.cd(self.velocity).get::<ratio::ratio>()
+    }
+    fn drag(&self) -> Acceleration {
+        self.simulation.drag_acceleration(self.velocity).norm()
+    }
+    // Drag, gravity and coriolis together, as applied for the next step
+    fn acceleration(&self) -> Acceleration {
+        self.simulation.acceleration(self.velocity).norm()
+    }
+    fn momentum(&self) -> Momentum {
+        self.velocity.norm() * self.simulation.projectile.mass()
+    }
+    fn speed_of_sound(&self) -> Velocity {
+        self.simulation.atmosphere.speed_of_sound()
+    }
+}
+
+impl Measurements for Sample {
+    fn time(&self) -> Time {
+        self.time
+    }
+    fn velocity(&self) -> Velocity {
+        self.velocity.norm()
+    }
+    fn energy(&self) -> Energy {
+        self.energy
+    }
+    fn height(&self) -> Length {
+        self.position.get_y()
+    }
+    fn relative_position(&self) -> MyVector3<length::Dimension> {
+        self.relative_position
+    }
+    fn mach(&self) -> Numeric {
+        self.mach
+    }
+    fn cd(&self) -> Numeric {
+        self.cd
+    }
+    fn drag(&self) -> Acceleration {
+        self.drag
+    }
+    fn acceleration(&self) -> Acceleration {
+        self.acceleration
+    }
+    fn momentum(&self) -> Momentum {
+        self.momentum
+    }
+    fn speed_of_sound(&self) -> Velocity {
+        self.speed_of_sound
+    }
+}
+
+pub trait Measurements {
+    fn time(&self) -> Time;
+    fn velocity(&self) -> Velocity;
+    fn energy(&self) -> Energy;
+    fn height(&self) -> Length;
+    fn relative_position(&self) -> MyVector3<length::Dimension>;
+    fn mach(&self) -> Numeric;
+    fn cd(&self) -> Numeric; // Coefficient of drag
+    fn drag(&self) -> Acceleration; // Deceleration from drag alone
+    fn acceleration(&self) -> Acceleration; // Total, including gravity
+    fn momentum(&self) -> Momentum;
+    fn speed_of_sound(&self) -> Velocity;
+
     // Positions relative to line of sight (shooter_pitch)
     fn distance(&self) -> Length {
         self.relative_position().get_x()
@@ -51,16 +223,6 @@ where
     fn horizontal_angle(&self, tolerance: Length) -> Angle {
         self.offset_horizontal_angle(Length::new::<meter>(0.0), tolerance)
     }
-    // During the simulation, the velocity of the projectile is rotated to allign with
-    // the shooter's bearing (azimuth and line of sight)
-    // This function returns the position rotated back to the initial frame of reference
-    // This is used during zero'ing and is output in the drop table
-    fn relative_position(&self) -> MyVector3<length::Dimension> {
-        self.position
-            .pivot_y(-self.simulation.shooter.yaw())
-            .pivot_z(-self.simulation.shooter.pitch())
-            .pivot_x(-self.simulation.shooter.roll())
-    }
     // This gives adjustment - opposite sign relative to desired offset
     // Always done in meters for now, due to relative_position()
     fn offset_vertical_angle(&self, offset: Length, tolerance: Length) -> Angle {
@@ -90,18 +252,3 @@ where
         position.angle(&desired) * sign
     }
 }
-
-pub trait Measurements {
-    fn time(&self) -> Time;
-    fn velocity(&self) -> Velocity;
-    fn energy(&self) -> Energy;
-    fn distance(&self) -> Length;
-    fn elevation(&self) -> Length;
-    fn windage(&self) -> Length;
-    fn angle(&self) -> Angle;
-    fn vertical_angle(&self, tolerance: Length) -> Angle;
-    fn horizontal_angle(&self, tolerance: Length) -> Angle;
-    fn relative_position(&self) -> MyVector3<length::Dimension>;
-    fn offset_vertical_angle(&self, offset: Length, tolerance: Length) -> Angle;
-    fn offset_horizontal_angle(&self, offset: Length, tolerance: Length) -> Angle;
-}
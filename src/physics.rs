@@ -34,11 +34,12 @@ where
         velocity - self.wind_velocity()
     }
     // Velocity relative to speed of sound (c), with given atmospheric conditions
-    fn mach(&self, velocity: MyVector3<velocity::Dimension>) -> Ratio {
-        velocity.norm() / self.atmosphere.speed_of_sound()
+    // Takes ground velocity, but it's speed through the air that counts, so wind is taken off first
+    pub(crate) fn mach(&self, velocity: MyVector3<velocity::Dimension>) -> Ratio {
+        self.vv(velocity).norm() / self.atmosphere.speed_of_sound()
     }
     // Coefficient of drag, as defined by a standard projectile depending on drag table used
-    fn cd(&self, velocity: MyVector3<velocity::Dimension>) -> Ratio {
+    pub(crate) fn cd(&self, velocity: MyVector3<velocity::Dimension>) -> Ratio {
         self.projectile.i()
             * self
                 .projectile
@@ -112,6 +113,22 @@ impl<T> Simulation<T> {
     }
 }
 
+// Total
+impl<T> Simulation<T>
+where
+    T: Projectile,
+{
+    // Everything acting on the projectile at this velocity, as used each step
+    pub(crate) fn acceleration(
+        &self,
+        velocity: MyVector3<velocity::Dimension>,
+    ) -> MyVector3<acceleration::Dimension> {
+        self.coriolis_acceleration(velocity)
+            + self.drag_acceleration(velocity)
+            + self.gravity_acceleration()
+    }
+}
+
 type EnergyPerTempPerAmount = MyQuantity<ISQ<P2, P1, N2, Z0, N1, N1, Z0>>;
 // Helpers - maybe some of these should be moved?
 impl Atmosphere {
@@ -217,6 +234,19 @@ impl Shooter {
     pub(crate) fn roll(&self) -> Angle {
         -self.roll
     }
+    // Velocity of the platform fired from, in the same frame as the projectile
+    // Heading follows bearing, so 90 degrees is moving to the right, and is flipped the same way
+    // Only bearing is applied - platform moves over the ground, regardless of line of sight
+    pub(crate) fn velocity(&self) -> MyVector3<velocity::Dimension> {
+        MyVector3::new(
+            self.velocity,
+            Velocity::new::<meter_per_second>(0.0),
+            Velocity::new::<meter_per_second>(0.0),
+        )
+        .pivot_z(self.climb)
+        .pivot_y(-self.heading)
+        .pivot_y(self.yaw())
+    }
     // Angular velocity vector of earth, at current lattitude
     // Can be thought of as vector from center of earth, pointing
     // to lines of lattitude.  Maximum effect at +/-90 degrees (poles)
@@ -0,0 +1,339 @@
+use crate::{
+    error::{Error, Result},
+    events::Event,
+    export::Column,
+    output::{Measurements, Sample},
+    projectiles::Projectile,
+    simulation::Simulation,
+    units::{foot_per_second, foot_pound, inch, meter, yard, Length},
+    Numeric,
+};
+
+use std::{fmt::Write as _, io::Write};
+
+// Plot area within the image, in pixels
+const WIDTH: Numeric = 800.0;
+const HEIGHT: Numeric = 480.0;
+const LEFT: Numeric = 80.0;
+const RIGHT: Numeric = 30.0;
+const TOP: Numeric = 50.0;
+const BOTTOM: Numeric = 60.0;
+const TICKS: Numeric = 8.0; // Roughly how many ticks along each axis
+
+// Trajectory sampled every step along line of sight, with each zero crossing found exactly
+// Charts of any of the measurements can then be drawn from the one run
+#[derive(Debug, Clone)]
+pub struct Plot {
+    samples: Vec<Sample>,
+    near_zeros: Vec<Sample>, // Rising through line of sight
+    far_zeros: Vec<Sample>,  // Falling through line of sight
+}
+impl Plot {
+    pub fn samples(&self) -> &[Sample] {
+        &self.samples
+    }
+    pub fn near_zeros(&self) -> &[Sample] {
+        &self.near_zeros
+    }
+    pub fn far_zeros(&self) -> &[Sample] {
+        &self.far_zeros
+    }
+}
+
+impl<T> Simulation<T>
+where
+    T: Projectile,
+{
+    // Samples from the muzzle to end (inclusive) every step, same stopping as range_card
+    pub fn plot(&self, end: Length, step: Length) -> Result<Plot> {
+        if end <= Length::new::<meter>(0.0) {
+            return Err(Error::PositiveExpected(end.get::<meter>()));
+        } else if step <= Length::new::<meter>(0.0) {
+            return Err(Error::PositiveExpected(step.get::<meter>()));
+        }
+        let distances = (0..)
+            .map(|index| step * index as Numeric)
+            .take_while(|distance| *distance <= end);
+        let samples = self
+            .packets_at(distances, end)
+            .into_iter()
+            .map(Sample::from)
+            .collect();
+
+        let (mut near_zeros, mut far_zeros) = (Vec::new(), Vec::new());
+        let events = self
+            .iter()
+            .max_distance(end)
+            .until(|p| p.plunging(Length::new::<meter>(0.0)))
+            .events();
+        for event in events {
+            match event {
+                Event::NearZero(packet) => near_zeros.push(packet.into()),
+                Event::FarZero(packet) => far_zeros.push(packet.into()),
+                Event::Apex(_) => (),
+            }
+        }
+        Ok(Plot {
+            samples,
+            near_zeros,
+            far_zeros,
+        })
+    }
+}
+
+// Single chart, some measurement against another, usually distance
+#[derive(Debug)]
+pub struct Chart {
+    title: &'static str,
+    x: Column<Sample>,
+    y: Column<Sample>,
+    line_of_sight: bool, // Draw y = 0 as the line of sight, always kept in view
+}
+impl Chart {
+    pub fn new(title: &'static str, x: Column<Sample>, y: Column<Sample>) -> Self {
+        Self {
+            title,
+            x,
+            y,
+            line_of_sight: false,
+        }
+    }
+    pub fn with_line_of_sight(mut self) -> Self {
+        self.line_of_sight = true;
+        self
+    }
+    // Usual charts against distance, in yards, inches, feet per second and foot pounds
+    pub fn elevation() -> Self {
+        Self::new(
+            "Elevation",
+            Column::length::<yard>("distance", |s: &Sample| s.distance()),
+            Column::length::<inch>("elevation", |s: &Sample| s.elevation()),
+        )
+        .with_line_of_sight()
+    }
+    pub fn windage() -> Self {
+        Self::new(
+            "Windage",
+            Column::length::<yard>("distance", |s: &Sample| s.distance()),
+            Column::length::<inch>("windage", |s: &Sample| s.windage()),
+        )
+        .with_line_of_sight()
+    }
+    pub fn velocity() -> Self {
+        Self::new(
+            "Velocity",
+            Column::length::<yard>("distance", |s: &Sample| s.distance()),
+            Column::velocity::<foot_per_second>("velocity", |s: &Sample| s.velocity()),
+        )
+    }
+    pub fn energy() -> Self {
+        Self::new(
+            "Energy",
+            Column::length::<yard>("distance", |s: &Sample| s.distance()),
+            Column::energy::<foot_pound>("energy", |s: &Sample| s.energy()),
+        )
+    }
+}
+
+// Axis from lo to hi, with ticks every step - bounds are rounded out to whole steps
+struct Axis {
+    lo: Numeric,
+    hi: Numeric,
+    step: Numeric,
+}
+impl Axis {
+    fn new<I>(values: I) -> Self
+    where
+        I: IntoIterator<Item = Numeric>,
+    {
+        let (mut min, mut max) = values.into_iter().filter(|value| value.is_finite()).fold(
+            (Numeric::INFINITY, Numeric::NEG_INFINITY),
+            |(min, max), value| (min.min(value), max.max(value)),
+        );
+        if min > max {
+            (min, max) = (0.0, 1.0);
+        } else if min == max {
+            (min, max) = (min - 1.0, max + 1.0);
+        }
+        // Smallest of 1, 2 or 5 times a power of ten giving about that many ticks
+        let rough = (max - min) / TICKS;
+        let power = Numeric::powf(10.0, rough.log10().floor());
+        let step = [1.0, 2.0, 5.0, 10.0]
+            .iter()
+            .map(|factor| factor * power)
+            .find(|step| *step >= rough)
+            .unwrap_or(10.0 * power);
+        Self {
+            lo: (min / step).floor() * step,
+            hi: (max / step).ceil() * step,
+            step,
+        }
+    }
+    fn ticks(&self) -> impl Iterator<Item = Numeric> + '_ {
+        let count = ((self.hi - self.lo) / self.step).round() as u64;
+        (0..=count).map(move |index| {
+            let value = self.lo + self.step * index as Numeric;
+            // No "-0" labels
+            if value.abs() < self.step * 1e-9 {
+                0.0
+            } else {
+                value
+            }
+        })
+    }
+    fn label(&self, value: Numeric) -> String {
+        let decimals = (-self.step.log10().floor()).max(0.0) as usize;
+        format!("{:.*}", decimals, value)
+    }
+    // Fraction of the way along the axis
+    fn scale(&self, value: Numeric) -> Numeric {
+        (value - self.lo) / (self.hi - self.lo)
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Plot {
+    // Self-contained SVG, with zero crossings marked on every chart
+    pub fn svg(&self, chart: &Chart) -> String {
+        let (x, y) = (&chart.x, &chart.y);
+        let x_axis = Axis::new(self.samples.iter().map(|s| x.value(s)));
+        let zero = chart.line_of_sight.then_some(0.0);
+        let y_axis = Axis::new(self.samples.iter().map(|s| y.value(s)).chain(zero));
+        let px = |value: Numeric| LEFT + x_axis.scale(value) * (WIDTH - LEFT - RIGHT);
+        let py = |value: Numeric| HEIGHT - BOTTOM - y_axis.scale(value) * (HEIGHT - TOP - BOTTOM);
+        let (left, right, top, bottom) = (LEFT, WIDTH - RIGHT, TOP, HEIGHT - BOTTOM);
+
+        // Writing to a String can't fail
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="12">"#,
+            w = WIDTH,
+            h = HEIGHT
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect width="{}" height="{}" fill="white"/>"#,
+            WIDTH, HEIGHT
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle" font-size="16">{}</text>"#,
+            WIDTH / 2.0,
+            TOP / 2.0 + 6.0,
+            escape(chart.title)
+        );
+
+        // Grid and tick labels
+        for tick in x_axis.ticks() {
+            let _ = writeln!(
+                svg,
+                r##"<line x1="{x:.1}" y1="{top}" x2="{x:.1}" y2="{bottom}" stroke="#ddd"/><text x="{x:.1}" y="{}" text-anchor="middle">{}</text>"##,
+                bottom + 18.0,
+                x_axis.label(tick),
+                x = px(tick),
+            );
+        }
+        for tick in y_axis.ticks() {
+            let _ = writeln!(
+                svg,
+                r##"<line x1="{left}" y1="{y:.1}" x2="{right}" y2="{y:.1}" stroke="#ddd"/><text x="{}" y="{:.1}" text-anchor="end">{}</text>"##,
+                left - 8.0,
+                py(tick) + 4.0,
+                y_axis.label(tick),
+                y = py(tick),
+            );
+        }
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{left}" y="{top}" width="{}" height="{}" fill="none" stroke="black"/>"#,
+            right - left,
+            bottom - top
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle">{} ({})</text>"#,
+            (left + right) / 2.0,
+            HEIGHT - 15.0,
+            escape(x.name()),
+            escape(x.unit())
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text transform="translate(20 {}) rotate(-90)" text-anchor="middle">{} ({})</text>"#,
+            (top + bottom) / 2.0,
+            escape(y.name()),
+            escape(y.unit())
+        );
+
+        if chart.line_of_sight {
+            let _ = writeln!(
+                svg,
+                r##"<line x1="{left}" y1="{y:.1}" x2="{right}" y2="{y:.1}" stroke="#1f77b4" stroke-dasharray="6 4"/><text x="{}" y="{:.1}" text-anchor="end" fill="#1f77b4">line of sight</text>"##,
+                right - 4.0,
+                py(0.0) - 4.0,
+                y = py(0.0),
+            );
+        }
+
+        // Trajectory, breaking the line anywhere a value isn't finite
+        let mut path = String::new();
+        let mut pen = false;
+        for sample in &self.samples {
+            let (sx, sy) = (x.value(sample), y.value(sample));
+            if sx.is_finite() && sy.is_finite() {
+                let _ = write!(
+                    path,
+                    "{}{:.1} {:.1} ",
+                    if pen { "L" } else { "M" },
+                    px(sx),
+                    py(sy)
+                );
+                pen = true;
+            } else {
+                pen = false;
+            }
+        }
+        let _ = writeln!(
+            svg,
+            r##"<path d="{}" fill="none" stroke="#d62728" stroke-width="2"/>"##,
+            path.trim_end()
+        );
+
+        let near = self.near_zeros.iter().map(|sample| ("near zero", sample));
+        let far = self.far_zeros.iter().map(|sample| ("far zero", sample));
+        for (label, sample) in near.chain(far) {
+            let (sx, sy) = (x.value(sample), y.value(sample));
+            if !(sx.is_finite() && sy.is_finite()) {
+                continue;
+            }
+            let _ = writeln!(
+                svg,
+                r##"<line x1="{x:.1}" y1="{top}" x2="{x:.1}" y2="{bottom}" stroke="#2ca02c" stroke-dasharray="2 3"/><circle cx="{x:.1}" cy="{:.1}" r="4" fill="#2ca02c"/><text x="{:.1}" y="{}" fill="#2ca02c">{} {} {}</text>"##,
+                py(sy),
+                px(sx) + 4.0,
+                top + 14.0,
+                label,
+                x_axis.label(sx),
+                escape(x.unit()),
+                x = px(sx),
+            );
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+    pub fn write_svg<W>(&self, mut writer: W, chart: &Chart) -> Result<()>
+    where
+        W: Write,
+    {
+        writer.write_all(self.svg(chart).as_bytes())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,367 @@
+use crate::{
+    error::{Error, Result},
+    projectiles::{Projectile, ProjectileImpl},
+    reticle::{Focal, Reticle},
+    serialize::quantity,
+    simulation::{Simulation, SimulationBuilder},
+    units::{
+        kelvin, meter, ratio, Angle, Length, Mass, TemperatureInterval, ThermodynamicTemperature,
+        Velocity,
+    },
+    Numeric,
+};
+
+use serde::{Deserialize, Serialize};
+
+use std::{ops::DerefMut, str::FromStr};
+
+use uom::si::temperature_interval::kelvin as kelvin_interval;
+
+// Rifle, load and optic kept apart, so any of them can be swapped out for another
+// Written as TOML, with every quantity carrying its unit, ie, height = "1.5 in"
+// Saved profiles use base units, so loading and saving again gives back exactly the same values
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    pub rifle: Rifle,
+    pub load: Load,
+    pub optic: Optic,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rifle {
+    pub name: String,
+    #[serde(with = "quantity")]
+    pub twist: Length, // Barrel length per turn, negative for left hand twist (not used for drift yet)
+    #[serde(with = "quantity")]
+    pub scope_height: Length,
+    #[serde(with = "quantity", default = "zero_length")]
+    pub scope_offset: Length,
+    pub zero: Zero,
+}
+
+// Where the rifle is zeroed, and the scope angles found for it, once they have been
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Zero {
+    #[serde(with = "quantity")]
+    pub distance: Length,
+    #[serde(with = "quantity", default = "zero_length")]
+    pub elevation: Length, // Offset above line of sight at distance
+    #[serde(with = "quantity", default = "zero_length")]
+    pub windage: Length, // Offset right of line of sight at distance
+    #[serde(
+        with = "quantity::option",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub pitch: Option<Angle>,
+    #[serde(
+        with = "quantity::option",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub yaw: Option<Angle>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DragTable {
+    G1,
+    G2,
+    G5,
+    G6,
+    G7,
+    G8,
+    GI,
+    GS,
+}
+impl DragTable {
+    // Same name the projectile type gives, ie, G7::DRAG_TABLE
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::G1 => "G1",
+            Self::G2 => "G2",
+            Self::G5 => "G5",
+            Self::G6 => "G6",
+            Self::G7 => "G7",
+            Self::G8 => "G8",
+            Self::GI => "GI",
+            Self::GS => "GS",
+        }
+    }
+}
+impl FromStr for DragTable {
+    type Err = Error;
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "G1" => Ok(Self::G1),
+            "G2" => Ok(Self::G2),
+            "G5" => Ok(Self::G5),
+            "G6" => Ok(Self::G6),
+            "G7" => Ok(Self::G7),
+            "G8" => Ok(Self::G8),
+            "GI" => Ok(Self::GI),
+            "GS" => Ok(Self::GS),
+            _ => Err(Error::Profile(format!("unknown drag table {:?}", value))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Load {
+    pub name: String,
+    pub drag: DragTable, // Drag table the bc is for, pick the matching projectile type to build
+    #[serde(with = "quantity")]
+    pub caliber: Length,
+    #[serde(with = "quantity")]
+    pub weight: Mass,
+    pub bc: Numeric,
+    #[serde(with = "quantity")]
+    pub velocity: Velocity, // Muzzle velocity, as measured at temperature
+    #[serde(with = "quantity")]
+    pub temperature: ThermodynamicTemperature, // Powder temperature velocity was measured at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sensitivity: Option<Sensitivity>,
+}
+
+// Change in muzzle velocity for a change in powder temperature, ie, 1.5 ft/s per °F
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Sensitivity {
+    #[serde(with = "quantity")]
+    pub velocity: Velocity,
+    #[serde(with = "quantity")]
+    pub temperature: TemperatureInterval,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Optic {
+    pub name: String,
+    #[serde(with = "quantity")]
+    pub elevation_click: Angle,
+    #[serde(default = "one")]
+    pub elevation_scale: Numeric,
+    #[serde(
+        with = "quantity::option",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub elevation_travel: Option<Angle>,
+    #[serde(with = "quantity")]
+    pub windage_click: Angle,
+    #[serde(default = "one")]
+    pub windage_scale: Numeric,
+    #[serde(
+        with = "quantity::option",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub windage_travel: Option<Angle>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reticle: Option<ReticleKind>,
+}
+
+// Reticle as described in a profile, built into a Reticle when needed
+// Design magnification is only given for second focal plane reticles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum ReticleKind {
+    MilDot {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        design: Option<Numeric>,
+    },
+    Tree {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        design: Option<Numeric>,
+        #[serde(with = "quantity")]
+        spacing: Angle,
+        rows: u32,
+        columns: u32,
+    },
+    Bdc {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        design: Option<Numeric>,
+        holds: Vec<Hold>,
+    },
+}
+
+// Single bdc hold, below center
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Hold(#[serde(with = "quantity")] pub Angle);
+
+fn zero_length() -> Length {
+    Length::new::<meter>(0.0)
+}
+fn one() -> Numeric {
+    1.0
+}
+
+impl ReticleKind {
+    pub fn reticle(&self) -> Result<Reticle> {
+        let focal = |design: Option<Numeric>| design.map_or(Focal::First, Focal::Second);
+        match self {
+            Self::MilDot { design } => Reticle::mil_dot(focal(*design)),
+            Self::Tree {
+                design,
+                spacing,
+                rows,
+                columns,
+            } => Reticle::tree(focal(*design), *spacing, *rows, *columns),
+            Self::Bdc { design, holds } => {
+                let holds = holds.iter().map(|hold| hold.0).collect::<Vec<_>>();
+                Reticle::bdc(focal(*design), &holds)
+            }
+        }
+    }
+}
+impl Optic {
+    pub fn reticle(&self) -> Option<Result<Reticle>> {
+        self.reticle.as_ref().map(ReticleKind::reticle)
+    }
+}
+
+impl Load {
+    // Muzzle velocity at another powder temperature, linear from where it was measured
+    pub fn velocity_at(&self, temperature: ThermodynamicTemperature) -> Velocity {
+        match self.sensitivity {
+            Some(sensitivity) => {
+                let change = TemperatureInterval::new::<kelvin_interval>(
+                    temperature.get::<kelvin>() - self.temperature.get::<kelvin>(),
+                );
+                self.velocity
+                    + sensitivity.velocity
+                        * (change / sensitivity.temperature).get::<ratio::ratio>()
+            }
+            None => self.velocity,
+        }
+    }
+}
+
+impl Profile {
+    pub fn from_toml(value: &str) -> Result<Self> {
+        toml::from_str(value).map_err(|err| Error::Profile(err.to_string()))
+    }
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string(self).map_err(|err| Error::Profile(err.to_string()))
+    }
+    // Builder with the rifle, load and optic set, and the zero's scope angles if it has them
+    // Projectile type must match the load's drag table - velocity is as measured, see
+    // Load::velocity_at for other powder temperatures
+    pub fn builder<T>(&self) -> Result<SimulationBuilder<T>>
+    where
+        T: Projectile + From<ProjectileImpl> + DerefMut<Target = ProjectileImpl>,
+    {
+        let (rifle, load, optic) = (&self.rifle, &self.load, &self.optic);
+        if load.drag.name() != T::DRAG_TABLE {
+            return Err(Error::DragTable {
+                expected: T::DRAG_TABLE,
+                found: load.drag.name().to_string(),
+            });
+        }
+        let mut builder = SimulationBuilder::new()
+            .set_caliber(load.caliber)?
+            .set_mass(load.weight)?
+            .set_bc(load.bc)?
+            .set_velocity(load.velocity)?
+            .set_scope_height(rifle.scope_height)
+            .set_scope_offset(rifle.scope_offset)
+            .set_elevation_click(optic.elevation_click)?
+            .set_elevation_scale(optic.elevation_scale)?
+            .set_windage_click(optic.windage_click)?
+            .set_windage_scale(optic.windage_scale)?;
+        if let Some(travel) = optic.elevation_travel {
+            builder = builder.set_elevation_travel(travel)?;
+        }
+        if let Some(travel) = optic.windage_travel {
+            builder = builder.set_windage_travel(travel)?;
+        }
+        if let Some(pitch) = rifle.zero.pitch {
+            builder = builder.set_scope_pitch(pitch);
+        }
+        if let Some(yaw) = rifle.zero.yaw {
+            builder = builder.set_scope_yaw(yaw);
+        }
+        Ok(builder)
+    }
+    // Zero the rifle under the builder's conditions, keeping the angles found
+    pub fn find_zero<T>(
+        &mut self,
+        builder: SimulationBuilder<T>,
+        tolerance: Length,
+    ) -> Result<Simulation<T>>
+    where
+        T: Projectile,
+    {
+        let zero = &mut self.rifle.zero;
+        let mut simulation = builder.init();
+        let (pitch, yaw) =
+            simulation.find_zero_angles(zero.distance, zero.elevation, zero.windage, tolerance)?;
+        zero.pitch = Some(pitch);
+        zero.yaw = Some(yaw);
+        Ok(simulation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        projectiles::{G1, G7},
+        units::{mil, radian},
+    };
+
+    const PROFILE: &str = r#"
+        [rifle]
+        name = "Bolt gun"
+        twist = "8 in"
+        scope_height = "1.75 in"
+
+        [rifle.zero]
+        distance = "100 m"
+
+        [load]
+        name = "6.5 Creedmoor, 140 gr"
+        drag = "G7"
+        caliber = "0.264 in"
+        weight = "140 gr"
+        bc = 0.305
+        velocity = "2710 ft/s"
+        temperature = "59 °F"
+
+        [optic]
+        name = "Mil/mil scope"
+        elevation_click = "0.1 mil"
+        windage_click = "0.1 mrad"
+    "#;
+
+    #[test]
+    fn mil_clicks_round_trip() {
+        let profile = Profile::from_toml(PROFILE).unwrap();
+        // Milliradians, not the NATO mil uom would otherwise read "mil" as
+        let click = Angle::new::<mil>(0.1);
+        assert!(
+            (profile.optic.elevation_click - click)
+                .get::<radian>()
+                .abs()
+                < 1e-15
+        );
+        assert!((profile.optic.windage_click - click).get::<radian>().abs() < 1e-15);
+
+        let again = Profile::from_toml(&profile.to_toml().unwrap()).unwrap();
+        assert_eq!(again.optic.elevation_click, profile.optic.elevation_click);
+        assert_eq!(again.optic.windage_click, profile.optic.windage_click);
+    }
+    #[test]
+    fn builder_checks_drag_table() {
+        let profile = Profile::from_toml(PROFILE).unwrap();
+        assert!(profile.builder::<G7>().is_ok());
+        assert!(matches!(
+            profile.builder::<G1>(),
+            Err(Error::DragTable { expected: "G1", ref found }) if found == "G7"
+        ));
+    }
+}
@@ -17,6 +17,9 @@ use lazy_static::lazy_static;
 pub type SectionalDensity = MyQuantity<ISQ<N2, P1, Z0, Z0, Z0, Z0, Z0>>;
 
 pub trait Projectile {
+    // Name of the drag table, ie, "G7" - stored with saved simulations, so never changes
+    const DRAG_TABLE: &'static str;
+
     fn area(&self) -> Area {
         PI * self.radius().powi(P2::new())
     }
@@ -32,6 +35,7 @@ pub trait Projectile {
     fn cd(&self, x: Numeric) -> Result<Numeric>;
 }
 
+#[derive(Debug, Clone)]
 pub struct ProjectileImpl {
     pub caliber: Length,
     pub weight: Mass,
@@ -46,6 +50,7 @@ macro_rules! drag_tables {
     ($($struct:ident => $module:ident),*) => {
         $(
             mod $module;
+            #[derive(Debug, Clone)]
             pub struct $struct(ProjectileImpl);
             impl From<ProjectileImpl> for $struct {
                 fn from(other: ProjectileImpl) -> Self {
@@ -65,6 +70,7 @@ macro_rules! drag_tables {
                 }
             }
             impl Projectile for $struct {
+                const DRAG_TABLE: &'static str = stringify!($struct);
                 fn velocity(&self) -> Velocity {
                     self.0.velocity
                 }
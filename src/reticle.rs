@@ -0,0 +1,173 @@
+use crate::{
+    error::{Error, Result},
+    output::Measurements,
+    units::{mil, radian, Angle, Length},
+    Numeric,
+};
+
+// Single hold point on a reticle, as the correction it gives when placed on the target
+// Same sign as Measurements::vertical_angle/horizontal_angle - a mark 2 mil below center gives
+// 2 mil of elevation, and a mark left of center gives windage to the right
+#[derive(Debug, Clone, Copy)]
+pub struct Mark {
+    elevation: Angle,
+    windage: Angle,
+}
+impl Mark {
+    pub fn new(elevation: Angle, windage: Angle) -> Self {
+        Self { elevation, windage }
+    }
+    pub fn elevation(&self) -> Angle {
+        self.elevation
+    }
+    pub fn windage(&self) -> Angle {
+        self.windage
+    }
+}
+
+// Focal plane the reticle sits in
+// Second focal plane reticles only subtend their marked values at one magnification
+#[derive(Debug, Clone, Copy)]
+pub enum Focal {
+    First,
+    Second(Numeric), // Magnification marks are true at
+}
+
+// Reticle as a set of hold points, at the current magnification
+#[derive(Debug, Clone)]
+pub struct Reticle {
+    marks: Vec<Mark>,
+    focal: Focal,
+    magnification: Numeric,
+}
+
+// Nearest hold point for a correction, and what is left over
+#[derive(Debug, Clone, Copy)]
+pub struct Hold {
+    mark: Mark,       // Mark to hold on, as marked on the reticle
+    actual: Mark,     // Correction the mark actually gives at current magnification
+    elevation: Angle, // Elevation left over after holding on the mark
+    windage: Angle,   // Windage left over after holding on the mark
+}
+impl Hold {
+    pub fn mark(&self) -> Mark {
+        self.mark
+    }
+    pub fn actual(&self) -> Mark {
+        self.actual
+    }
+    pub fn elevation(&self) -> Angle {
+        self.elevation
+    }
+    pub fn windage(&self) -> Angle {
+        self.windage
+    }
+}
+
+impl Reticle {
+    pub fn new(marks: Vec<Mark>, focal: Focal) -> Result<Self> {
+        match focal {
+            Focal::Second(magnification) if magnification <= 0.0 => {
+                Err(Error::PositiveExpected(magnification))
+            }
+            Focal::Second(magnification) => Ok(Self {
+                marks,
+                focal,
+                magnification,
+            }),
+            Focal::First => Ok(Self {
+                marks,
+                focal,
+                magnification: 1.0,
+            }),
+        }
+    }
+    // Dots every mil along each crosshair, out to 5 mil from center
+    pub fn mil_dot(focal: Focal) -> Result<Self> {
+        let marks = (-5..=5)
+            .map(|i| Mark::new(Angle::new::<mil>(i as Numeric), Angle::new::<radian>(0.0)))
+            .chain(
+                (-5..=5)
+                    .filter(|&i| i != 0)
+                    .map(|i| Mark::new(Angle::new::<radian>(0.0), Angle::new::<mil>(i as Numeric))),
+            )
+            .collect();
+        Self::new(marks, focal)
+    }
+    // Christmas tree grid - horizontal crosshair marked out to columns either side, then rows
+    // below center every spacing, each wider than the last, out to columns again on the bottom row
+    pub fn tree(focal: Focal, spacing: Angle, rows: u32, columns: u32) -> Result<Self> {
+        let mut marks = Vec::new();
+        for row in 0..=rows {
+            let width = if row == 0 {
+                columns
+            } else {
+                (columns * row).div_ceil(rows)
+            } as i64;
+            for column in -width..=width {
+                marks.push(Mark::new(
+                    spacing * row as Numeric,
+                    spacing * column as Numeric,
+                ));
+            }
+        }
+        Self::new(marks, focal)
+    }
+    // Bullet drop compensating reticle - marks below center only, at the given subtensions
+    pub fn bdc(focal: Focal, holds: &[Angle]) -> Result<Self> {
+        let marks = std::iter::once(Angle::new::<radian>(0.0))
+            .chain(holds.iter().copied())
+            .map(|elevation| Mark::new(elevation, Angle::new::<radian>(0.0)))
+            .collect();
+        Self::new(marks, focal)
+    }
+    // Current magnification - only changes subtensions for second focal plane reticles
+    pub fn set_magnification(mut self, value: Numeric) -> Result<Self> {
+        if value > 0.0 {
+            self.magnification = value;
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected(value))
+        }
+    }
+    // Scale from marked to actual subtension
+    fn scale(&self) -> Numeric {
+        match self.focal {
+            Focal::First => 1.0,
+            Focal::Second(design) => design / self.magnification,
+        }
+    }
+    // Nearest mark to the given corrections, None if the reticle has no marks
+    pub fn hold(&self, elevation: Angle, windage: Angle) -> Option<Hold> {
+        let scale = self.scale();
+        self.marks
+            .iter()
+            .map(|&mark| {
+                let actual = Mark::new(mark.elevation * scale, mark.windage * scale);
+                Hold {
+                    mark,
+                    actual,
+                    elevation: elevation - actual.elevation,
+                    windage: windage - actual.windage,
+                }
+            })
+            .min_by(|a, b| {
+                let error = |hold: &Hold| {
+                    hold.elevation
+                        .get::<radian>()
+                        .hypot(hold.windage.get::<radian>())
+                };
+                error(a).total_cmp(&error(b))
+            })
+    }
+    // Nearest mark for a packet (or sample), using its corrections from line of sight
+    pub fn hold_for<M>(&self, packet: &M, tolerance: Length) -> Option<Hold>
+    where
+        M: Measurements,
+    {
+        self.hold(
+            packet.vertical_angle(tolerance),
+            packet.horizontal_angle(tolerance),
+        )
+    }
+}
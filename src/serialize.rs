@@ -0,0 +1,499 @@
+use crate::{
+    error::{Error, Result},
+    projectiles::{Projectile, ProjectileImpl, G1},
+    simulation::{Atmosphere, Flags, Scope, Shooter, Simulation, SimulationBuilder, Turret, Wind},
+    units::{
+        joule, kelvin, kilogram, kilogram_meter_per_second, meter, meter_per_second,
+        meter_per_second_squared, parse_angle, pascal, radian, second, Acceleration, Angle,
+        Dimension, DisplayStyle, Energy, Length, Mass, Momentum, MyQuantity, Pressure,
+        TemperatureInterval, ThermodynamicTemperature, Time, Velocity,
+    },
+    vectors::{MyVector3, Vectors},
+    Numeric,
+};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use std::{
+    ops::{Deref, DerefMut},
+    result,
+};
+
+use uom::si::temperature_interval::kelvin as kelvin_interval;
+
+// Quantity written out in base units with its unit, ie, "0.0381 m" - so nothing is lost going
+// back and forth - but read back in any unit uom can parse, ie, "1.5 in", or for angles, "0.1 mil"
+#[derive(Clone, Copy)]
+pub(crate) struct Explicit<Q>(Q);
+
+// Same as Explicit, for fields of other serde types, ie, #[serde(with = "quantity")]
+pub(crate) mod quantity {
+    use super::*;
+
+    pub(crate) fn serialize<Q, S>(value: &Q, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        Q: Copy,
+        Explicit<Q>: Serialize,
+        S: Serializer,
+    {
+        Explicit(*value).serialize(serializer)
+    }
+    pub(crate) fn deserialize<'de, Q, D>(deserializer: D) -> result::Result<Q, D::Error>
+    where
+        Explicit<Q>: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Explicit::deserialize(deserializer).map(|Explicit(value)| value)
+    }
+
+    // Optional quantities, left out entirely when missing - only profiles have any so far
+    #[cfg(feature = "profile")]
+    pub(crate) mod option {
+        use super::*;
+
+        pub(crate) fn serialize<Q, S>(
+            value: &Option<Q>,
+            serializer: S,
+        ) -> result::Result<S::Ok, S::Error>
+        where
+            Q: Copy,
+            Explicit<Q>: Serialize,
+            S: Serializer,
+        {
+            value.map(Explicit).serialize(serializer)
+        }
+        pub(crate) fn deserialize<'de, Q, D>(deserializer: D) -> result::Result<Option<Q>, D::Error>
+        where
+            Explicit<Q>: Deserialize<'de>,
+            D: Deserializer<'de>,
+        {
+            Option::<Explicit<Q>>::deserialize(deserializer)
+                .map(|value| value.map(|Explicit(value)| value))
+        }
+    }
+}
+
+// Read back with uom's FromStr, unless the quantity has its own parser, ie, Angle => radian as parse
+macro_rules! explicit {
+    (@parse) => {
+        str::parse
+    };
+    (@parse $parse:path) => {
+        $parse
+    };
+    ($($quantity:ident => $unit:ident $(as $parse:path)?,)+) => {
+        $(
+            impl Serialize for Explicit<$quantity> {
+                fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    serializer.collect_str(
+                        &self.0.into_format_args($unit, DisplayStyle::Abbreviation),
+                    )
+                }
+            }
+            impl<'de> Deserialize<'de> for Explicit<$quantity> {
+                fn deserialize<D>(deserializer: D) -> result::Result<Self, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    let value = String::deserialize(deserializer)?;
+                    let parse = explicit!(@parse $($parse)?);
+                    parse(&value).map(Explicit).map_err(|err| {
+                        de::Error::custom(format!("{:?} parsing {:?}", err, value))
+                    })
+                }
+            }
+        )+
+    };
+}
+explicit! {
+    Acceleration => meter_per_second_squared,
+    Angle => radian as parse_angle,
+    Energy => joule,
+    Length => meter,
+    Mass => kilogram,
+    Momentum => kilogram_meter_per_second,
+    Pressure => pascal,
+    TemperatureInterval => kelvin_interval,
+    ThermodynamicTemperature => kelvin,
+    Time => second,
+    Velocity => meter_per_second,
+}
+
+// Vectors as [x, y, z], each an Explicit quantity
+impl<D: ?Sized> Serialize for MyVector3<D>
+where
+    D: Dimension,
+    Explicit<MyQuantity<D>>: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        [
+            Explicit(self.get_x()),
+            Explicit(self.get_y()),
+            Explicit(self.get_z()),
+        ]
+        .serialize(serializer)
+    }
+}
+impl<'de, D: ?Sized> Deserialize<'de> for MyVector3<D>
+where
+    D: Dimension,
+    Explicit<MyQuantity<D>>: Deserialize<'de>,
+{
+    fn deserialize<D2>(deserializer: D2) -> result::Result<Self, D2::Error>
+    where
+        D2: Deserializer<'de>,
+    {
+        let [x, y, z] = <[Explicit<MyQuantity<D>>; 3]>::deserialize(deserializer)?;
+        Ok(Self::new(x.0, y.0, z.0))
+    }
+}
+
+// Plain copies of each part of a simulation, in the shape they are written out
+// Reading one back goes through SimulationBuilder's setters, so everything is checked the same
+// way it would be when built by hand
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FlagsDef {
+    coriolis: bool,
+    drag: bool,
+    gravity: bool,
+}
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProjectileDef {
+    caliber: Explicit<Length>,
+    weight: Explicit<Mass>,
+    bc: Numeric,
+    velocity: Explicit<Velocity>,
+}
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TurretDef {
+    click: Explicit<Angle>,
+    scale: Numeric,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    travel: Option<Explicit<Angle>>,
+}
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScopeDef {
+    yaw: Explicit<Angle>,
+    pitch: Explicit<Angle>,
+    roll: Explicit<Angle>,
+    height: Explicit<Length>,
+    offset: Explicit<Length>,
+    elevation: TurretDef,
+    windage: TurretDef,
+}
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AtmosphereDef {
+    temperature: Explicit<ThermodynamicTemperature>,
+    pressure: Explicit<Pressure>,
+    humidity: Numeric,
+}
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WindDef {
+    velocity: Explicit<Velocity>,
+    yaw: Explicit<Angle>,
+}
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ShooterDef {
+    yaw: Explicit<Angle>,
+    pitch: Explicit<Angle>,
+    roll: Explicit<Angle>,
+    lattitude: Explicit<Angle>,
+    gravity: Explicit<Acceleration>,
+    velocity: Explicit<Velocity>,
+    heading: Explicit<Angle>,
+    climb: Explicit<Angle>,
+}
+// Model and time step first, as some formats (ie, TOML) need plain values ahead of any tables
+// Model is the drag table, which has to match the projectile type being read into
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SimulationDef {
+    model: String,
+    time_step: Explicit<Time>,
+    flags: FlagsDef,
+    projectile: ProjectileDef,
+    scope: ScopeDef,
+    atmosphere: AtmosphereDef,
+    wind: WindDef,
+    shooter: ShooterDef,
+}
+
+impl From<&Flags> for FlagsDef {
+    fn from(other: &Flags) -> Self {
+        Self {
+            coriolis: other.coriolis,
+            drag: other.drag,
+            gravity: other.gravity,
+        }
+    }
+}
+impl From<&ProjectileImpl> for ProjectileDef {
+    fn from(other: &ProjectileImpl) -> Self {
+        Self {
+            caliber: Explicit(other.caliber),
+            weight: Explicit(other.weight),
+            bc: other.bc,
+            velocity: Explicit(other.velocity),
+        }
+    }
+}
+impl From<&Scope> for ScopeDef {
+    fn from(other: &Scope) -> Self {
+        let turret = |turret: &Turret| TurretDef {
+            click: Explicit(turret.click),
+            scale: turret.scale,
+            travel: turret.travel.map(Explicit),
+        };
+        Self {
+            yaw: Explicit(other.yaw),
+            pitch: Explicit(other.pitch),
+            roll: Explicit(other.roll),
+            height: Explicit(other.height),
+            offset: Explicit(other.offset),
+            elevation: turret(&other.elevation),
+            windage: turret(&other.windage),
+        }
+    }
+}
+impl From<&Atmosphere> for AtmosphereDef {
+    fn from(other: &Atmosphere) -> Self {
+        Self {
+            temperature: Explicit(other.temperature),
+            pressure: Explicit(other.pressure),
+            humidity: other.humidity,
+        }
+    }
+}
+impl From<&Wind> for WindDef {
+    fn from(other: &Wind) -> Self {
+        Self {
+            velocity: Explicit(other.velocity),
+            yaw: Explicit(other.yaw),
+        }
+    }
+}
+impl From<&Shooter> for ShooterDef {
+    fn from(other: &Shooter) -> Self {
+        Self {
+            yaw: Explicit(other.yaw),
+            pitch: Explicit(other.pitch),
+            roll: Explicit(other.roll),
+            lattitude: Explicit(other.lattitude),
+            gravity: Explicit(other.gravity),
+            velocity: Explicit(other.velocity),
+            heading: Explicit(other.heading),
+            climb: Explicit(other.climb),
+        }
+    }
+}
+impl<T> From<&Simulation<T>> for SimulationDef
+where
+    T: Projectile + Deref<Target = ProjectileImpl>,
+{
+    fn from(other: &Simulation<T>) -> Self {
+        Self {
+            model: T::DRAG_TABLE.to_string(),
+            time_step: Explicit(other.time_step),
+            flags: FlagsDef::from(&other.flags),
+            projectile: ProjectileDef::from(other.projectile.deref()),
+            scope: ScopeDef::from(&other.scope),
+            atmosphere: AtmosphereDef::from(&other.atmosphere),
+            wind: WindDef::from(&other.wind),
+            shooter: ShooterDef::from(&other.shooter),
+        }
+    }
+}
+
+impl FlagsDef {
+    fn apply<T>(self, builder: SimulationBuilder<T>) -> Result<SimulationBuilder<T>> {
+        Ok(builder
+            .use_coriolis(self.coriolis)
+            .use_drag(self.drag)
+            .use_gravity(self.gravity))
+    }
+}
+impl ProjectileDef {
+    fn apply<T>(self, builder: SimulationBuilder<T>) -> Result<SimulationBuilder<T>>
+    where
+        T: DerefMut<Target = ProjectileImpl>,
+    {
+        builder
+            .set_caliber(self.caliber.0)?
+            .set_mass(self.weight.0)?
+            .set_bc(self.bc)?
+            .set_velocity(self.velocity.0)
+    }
+}
+impl ScopeDef {
+    fn apply<T>(self, builder: SimulationBuilder<T>) -> Result<SimulationBuilder<T>> {
+        let mut builder = builder
+            .set_scope_yaw(self.yaw.0)
+            .set_scope_pitch(self.pitch.0)
+            .set_scope_roll(self.roll.0)
+            .set_scope_height(self.height.0)
+            .set_scope_offset(self.offset.0)
+            .set_elevation_click(self.elevation.click.0)?
+            .set_elevation_scale(self.elevation.scale)?
+            .set_windage_click(self.windage.click.0)?
+            .set_windage_scale(self.windage.scale)?;
+        if let Some(travel) = self.elevation.travel {
+            builder = builder.set_elevation_travel(travel.0)?;
+        }
+        if let Some(travel) = self.windage.travel {
+            builder = builder.set_windage_travel(travel.0)?;
+        }
+        Ok(builder)
+    }
+}
+impl AtmosphereDef {
+    fn apply<T>(self, builder: SimulationBuilder<T>) -> Result<SimulationBuilder<T>> {
+        builder
+            .set_temperature(self.temperature.0)?
+            .set_pressure(self.pressure.0)?
+            .set_humidity(self.humidity)
+    }
+}
+impl WindDef {
+    fn apply<T>(self, builder: SimulationBuilder<T>) -> Result<SimulationBuilder<T>> {
+        builder
+            .set_wind_speed(self.velocity.0)?
+            .set_wind_angle(self.yaw.0)
+    }
+}
+impl ShooterDef {
+    fn apply<T>(self, builder: SimulationBuilder<T>) -> Result<SimulationBuilder<T>> {
+        builder
+            .set_bearing(self.yaw.0)?
+            .set_shot_angle(self.pitch.0)?
+            .set_cant(self.roll.0)?
+            .set_lattitude(self.lattitude.0)?
+            .set_gravity(self.gravity.0)?
+            .set_platform_speed(self.velocity.0)?
+            .set_platform_heading(self.heading.0)?
+            .set_platform_climb(self.climb.0)
+    }
+}
+impl SimulationDef {
+    fn apply<T>(self, builder: SimulationBuilder<T>) -> Result<SimulationBuilder<T>>
+    where
+        T: Projectile + DerefMut<Target = ProjectileImpl>,
+    {
+        if self.model != T::DRAG_TABLE {
+            return Err(Error::DragTable {
+                expected: T::DRAG_TABLE,
+                found: self.model,
+            });
+        }
+        let builder = builder.set_time_step(self.time_step.0)?;
+        let builder = self.flags.apply(builder)?;
+        let builder = self.projectile.apply(builder)?;
+        let builder = self.scope.apply(builder)?;
+        let builder = self.atmosphere.apply(builder)?;
+        let builder = self.wind.apply(builder)?;
+        self.shooter.apply(builder)
+    }
+}
+
+// Parts read on their own are checked by applying them to a default builder, then taken back out
+// Drag table doesn't matter here, G1 is just something the projectile setters will take
+fn check<F>(apply: F) -> Result<Simulation<G1>>
+where
+    F: FnOnce(SimulationBuilder<G1>) -> Result<SimulationBuilder<G1>>,
+{
+    apply(SimulationBuilder::new()).map(SimulationBuilder::init)
+}
+
+macro_rules! via {
+    ($($type:ty => $def:ident, |$simulation:ident| $part:expr;)+) => {
+        $(
+            impl Serialize for $type {
+                fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    $def::from(self).serialize(serializer)
+                }
+            }
+            impl<'de> Deserialize<'de> for $type {
+                fn deserialize<D>(deserializer: D) -> result::Result<Self, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    let def = $def::deserialize(deserializer)?;
+                    check(|builder| def.apply(builder))
+                        .map(|$simulation| $part)
+                        .map_err(de::Error::custom)
+                }
+            }
+        )+
+    };
+}
+via! {
+    Flags => FlagsDef, |simulation| simulation.flags;
+    ProjectileImpl => ProjectileDef, |simulation| simulation.projectile.deref().clone();
+    Scope => ScopeDef, |simulation| simulation.scope;
+    Atmosphere => AtmosphereDef, |simulation| simulation.atmosphere;
+    Wind => WindDef, |simulation| simulation.wind;
+    Shooter => ShooterDef, |simulation| simulation.shooter;
+}
+
+impl<T> Serialize for SimulationBuilder<T>
+where
+    T: Projectile + Deref<Target = ProjectileImpl>,
+{
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.builder.serialize(serializer)
+    }
+}
+impl<'de, T> Deserialize<'de> for SimulationBuilder<T>
+where
+    T: Projectile + From<ProjectileImpl> + DerefMut<Target = ProjectileImpl>,
+{
+    fn deserialize<D>(deserializer: D) -> result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        SimulationDef::deserialize(deserializer)?
+            .apply(SimulationBuilder::new())
+            .map_err(de::Error::custom)
+    }
+}
+
+impl<T> Serialize for Simulation<T>
+where
+    T: Projectile + Deref<Target = ProjectileImpl>,
+{
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        SimulationDef::from(self).serialize(serializer)
+    }
+}
+impl<'de, T> Deserialize<'de> for Simulation<T>
+where
+    T: Projectile + From<ProjectileImpl> + DerefMut<Target = ProjectileImpl>,
+{
+    fn deserialize<D>(deserializer: D) -> result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        SimulationBuilder::deserialize(deserializer).map(SimulationBuilder::init)
+    }
+}
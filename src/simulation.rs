@@ -6,16 +6,16 @@ use crate::{
     projectiles::ProjectileImpl,
     units::{
         celsius, fahrenheit, foot_per_second, grain, inch, inch_of_mercury, kelvin, kilogram,
-        meter, meter_per_second, meter_per_second_squared, mile_per_hour, pascal, radian, second,
-        Acceleration, Angle, Length, Mass, MyQuantity, Pressure, ThermodynamicTemperature, Time,
-        Velocity,
+        meter, meter_per_second, meter_per_second_squared, mile_per_hour, moa, pascal, radian,
+        second, Acceleration, Angle, Length, Mass, MyQuantity, Pressure, ThermodynamicTemperature,
+        Time, Velocity,
     },
     Numeric,
 };
 
 use std::ops::DerefMut;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Simulation<T> {
     pub(crate) flags: Flags, // Flags to enable/disable certain parts of simulation
     pub(crate) projectile: T, // Use same projectile for zeroing and solving
@@ -25,42 +25,53 @@ pub struct Simulation<T> {
     pub(crate) shooter: Shooter, // Different conditions during solving
     pub(crate) time_step: Time, // Use same timestep for zeroing and solving
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Atmosphere {
     pub(crate) temperature: ThermodynamicTemperature, // Temperature (F)
     pub(crate) pressure: Pressure,                    // Pressure (InHg)
     pub(crate) humidity: Numeric,                     // Humidity (0-1)
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Flags {
     pub(crate) coriolis: bool, // Whether or not to calculate coriolis/eotvos effect
     pub(crate) drag: bool,     // Whether or not to calculate drag
     pub(crate) gravity: bool,  // Whether or not to calculate gravity
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Scope {
     pub(crate) yaw: Angle,
     pub(crate) pitch: Angle,
-    pub(crate) roll: Angle,    // Scope Roll (Cant) (Degrees)
-    pub(crate) height: Length, // Scope Height (inches)
-    pub(crate) offset: Length, // Scope Offset Windage (left/right boreline) (inches)
+    pub(crate) roll: Angle,       // Scope Roll (Cant) (Degrees)
+    pub(crate) height: Length,    // Scope Height (inches)
+    pub(crate) offset: Length,    // Scope Offset Windage (left/right boreline) (inches)
+    pub(crate) elevation: Turret, // Elevation Turret
+    pub(crate) windage: Turret,   // Windage Turret
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+pub struct Turret {
+    pub(crate) click: Angle,          // Click Value, ie, 1/4 MOA or 0.1 mil
+    pub(crate) scale: Numeric,        // Tracking Scale, actual per dialed (1.0 tracks true)
+    pub(crate) travel: Option<Angle>, // Maximum Travel from zero, either direction
+}
+#[derive(Debug, Clone)]
 pub struct Shooter {
     pub(crate) yaw: Angle, // Bearing (0 North, 90 East) (degrees) (Coriolis/Eotvos Effect)
     pub(crate) pitch: Angle, // Line of Sight angle (degrees)
     pub(crate) roll: Angle, // Roll relative to shooters position, ie, scope alligned with rifle
     pub(crate) lattitude: Angle, // Lattitude (Coriolis/Eotvos Effect)
     pub(crate) gravity: Acceleration, // Gravity (m/s^2)
+    pub(crate) velocity: Velocity, // Platform Velocity, ie, moving vehicle (miles/hour)
+    pub(crate) heading: Angle, // Platform Direction of Travel, relative to bearing (degrees)
+    pub(crate) climb: Angle, // Platform Climb Angle (degrees)
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Wind {
     pub(crate) yaw: Angle,         // Wind Angle (degrees)
     pub(crate) pitch: Angle,       // Wind Pitch (degrees)
     pub(crate) roll: Angle,        // Doesn make sense, just here for consistency
     pub(crate) velocity: Velocity, // Wind Velocity (miles/hour)
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SimulationBuilder<T> {
     pub(crate) builder: Simulation<T>,
 }
@@ -74,89 +85,79 @@ impl<T> From<Simulation<T>> for SimulationBuilder<T> {
         Self { builder: other }
     }
 }
-impl<T> Default for SimulationBuilder<T>
-where
-    T: From<ProjectileImpl>,
-{
+// Conditions which may change between zeroing and firing - atmosphere, wind and shooter
+// Zero under one set (ie, at the home range), then take the zeroed simulation into the field
+// with another, keeping projectile, scope angles, flags and time step
+// Built on their own with the same checked setters as SimulationBuilder, ie,
+// Conditions::new().set_temperature(..)?.set_wind_speed(..)?
+#[derive(Debug, Clone)]
+pub struct Conditions {
+    pub(crate) atmosphere: Atmosphere,
+    pub(crate) wind: Wind,
+    pub(crate) shooter: Shooter,
+}
+impl<T> Simulation<T> {
+    pub fn conditions(&self) -> Conditions {
+        Conditions {
+            atmosphere: self.atmosphere.clone(),
+            wind: self.wind.clone(),
+            shooter: self.shooter.clone(),
+        }
+    }
+    pub fn set_conditions(&mut self, value: Conditions) {
+        self.atmosphere = value.atmosphere;
+        self.wind = value.wind;
+        self.shooter = value.shooter;
+    }
+    // Copy of this simulation under other conditions, leaving this one (and its zero) as is
+    pub fn with_conditions(&self, value: Conditions) -> Self
+    where
+        T: Clone,
+    {
+        let mut simulation = self.clone();
+        simulation.set_conditions(value);
+        simulation
+    }
+}
+impl Default for Conditions {
     fn default() -> Self {
         Self {
-            builder: Simulation {
-                flags: Flags {
-                    coriolis: true,
-                    drag: true,
-                    gravity: true,
-                },
-                projectile: From::from(ProjectileImpl {
-                    caliber: Length::new::<inch>(0.264),
-                    weight: Mass::new::<grain>(140.0),
-                    bc: 0.305,
-                    velocity: Velocity::new::<foot_per_second>(2710.0),
-                }),
-                scope: Scope {
-                    yaw: Angle::new::<radian>(0.0),
-                    pitch: Angle::new::<radian>(0.0),
-                    roll: Angle::new::<radian>(0.0),
-                    height: Length::new::<inch>(1.5),
-                    offset: Length::new::<inch>(0.0),
-                },
-                atmosphere: Atmosphere {
-                    temperature: ThermodynamicTemperature::new::<fahrenheit>(68.0),
-                    pressure: Pressure::new::<inch_of_mercury>(29.92),
-                    humidity: 0.0,
-                },
-                wind: Wind {
-                    yaw: Angle::new::<radian>(0.0),
-                    pitch: Angle::new::<radian>(0.0),
-                    roll: Angle::new::<radian>(0.0),
-                    velocity: Velocity::new::<mile_per_hour>(0.0),
-                },
-                shooter: Shooter {
-                    yaw: Angle::new::<radian>(0.0),
-                    pitch: Angle::new::<radian>(0.0),
-                    roll: Angle::new::<radian>(0.0),
-                    lattitude: Angle::new::<radian>(0.0),
-                    gravity: my_quantity!(-9.806_65),
-                },
-                time_step: Time::new::<second>(0.000_001),
+            atmosphere: Atmosphere {
+                temperature: ThermodynamicTemperature::new::<fahrenheit>(68.0),
+                pressure: Pressure::new::<inch_of_mercury>(29.92),
+                humidity: 0.0,
+            },
+            wind: Wind {
+                yaw: Angle::new::<radian>(0.0),
+                pitch: Angle::new::<radian>(0.0),
+                roll: Angle::new::<radian>(0.0),
+                velocity: Velocity::new::<mile_per_hour>(0.0),
+            },
+            shooter: Shooter {
+                yaw: Angle::new::<radian>(0.0),
+                pitch: Angle::new::<radian>(0.0),
+                roll: Angle::new::<radian>(0.0),
+                lattitude: Angle::new::<radian>(0.0),
+                gravity: my_quantity!(-9.806_65),
+                velocity: Velocity::new::<mile_per_hour>(0.0),
+                heading: Angle::new::<radian>(0.0),
+                climb: Angle::new::<radian>(0.0),
             },
         }
     }
 }
-
-impl<T> SimulationBuilder<T>
-where
-    T: From<ProjectileImpl>,
-{
+impl Conditions {
+    // Same defaults as SimulationBuilder
     pub fn new() -> Self {
         Default::default()
     }
-}
-impl<T> SimulationBuilder<T> {
-    // Create simulation with conditions used to find muzzle_pitch for 'zeroing'
-    // Starting from flat fire pitch (0.0)
-    pub fn init(self) -> Simulation<T> {
-        From::from(self)
-    }
-    pub fn set_time_step(mut self, value: Time) -> Result<Self> {
-        let min = Time::new::<second>(0.0);
-        let max = Time::new::<second>(0.1);
-        if value > min && value <= max {
-            self.builder.time_step = value;
-            Ok(self)
-        } else {
-            Err(Error::OutOfRange {
-                min: min.get::<second>(),
-                max: max.get::<second>(),
-            })
-        }
-    }
 
     // Atmosphere
     pub fn set_temperature(mut self, value: ThermodynamicTemperature) -> Result<Self> {
         let min = ThermodynamicTemperature::new::<celsius>(-80.0);
         let max = ThermodynamicTemperature::new::<celsius>(50.0);
         if value >= min && value <= max {
-            self.builder.atmosphere.temperature = value;
+            self.atmosphere.temperature = value;
             Ok(self)
         } else {
             Err(Error::OutOfRange {
@@ -167,7 +168,7 @@ impl<T> SimulationBuilder<T> {
     }
     pub fn set_pressure(mut self, value: Pressure) -> Result<Self> {
         if value.is_sign_positive() {
-            self.builder.atmosphere.pressure = value;
+            self.atmosphere.pressure = value;
             Ok(self)
         } else {
             Err(Error::PositiveExpected(value.get::<pascal>()))
@@ -176,33 +177,32 @@ impl<T> SimulationBuilder<T> {
     pub fn set_humidity(mut self, value: Numeric) -> Result<Self> {
         let (min, max) = (0.0, 1.0);
         if value >= min && value <= max {
-            self.builder.atmosphere.humidity = value;
+            self.atmosphere.humidity = value;
             Ok(self)
         } else {
             Err(Error::OutOfRange { min, max })
         }
     }
 
-    // Flags
-    pub fn use_coriolis(mut self, value: bool) -> Self {
-        self.builder.flags.coriolis = value;
-        self
-    }
-    pub fn use_drag(mut self, value: bool) -> Self {
-        self.builder.flags.drag = value;
-        self
-    }
-    pub fn use_gravity(mut self, value: bool) -> Self {
-        self.builder.flags.gravity = value;
-        self
-    }
-
     // Shooter
     pub fn set_shot_angle(mut self, value: Angle) -> Result<Self> {
         let min = Angle::new::<radian>(-FRAC_PI_2);
         let max = Angle::new::<radian>(FRAC_PI_2);
         if value >= min && value <= max {
-            self.builder.shooter.pitch = value;
+            self.shooter.pitch = value;
+            Ok(self)
+        } else {
+            Err(Error::OutOfRange {
+                min: min.get::<radian>(),
+                max: max.get::<radian>(),
+            })
+        }
+    }
+    pub fn set_cant(mut self, value: Angle) -> Result<Self> {
+        let min = Angle::new::<radian>(-FRAC_PI_2);
+        let max = Angle::new::<radian>(FRAC_PI_2);
+        if value >= min && value <= max {
+            self.shooter.roll = value;
             Ok(self)
         } else {
             Err(Error::OutOfRange {
@@ -215,7 +215,7 @@ impl<T> SimulationBuilder<T> {
         let min = Angle::new::<radian>(-FRAC_PI_2);
         let max = Angle::new::<radian>(FRAC_PI_2);
         if value >= min && value <= max {
-            self.builder.shooter.lattitude = value;
+            self.shooter.lattitude = value;
             Ok(self)
         } else {
             Err(Error::OutOfRange {
@@ -228,7 +228,7 @@ impl<T> SimulationBuilder<T> {
         let min = Angle::new::<radian>(-2.0 * PI);
         let max = Angle::new::<radian>(2.0 * PI);
         if value >= min && value <= max {
-            self.builder.shooter.yaw = value;
+            self.shooter.yaw = value;
             Ok(self)
         } else {
             Err(Error::OutOfRange {
@@ -239,7 +239,7 @@ impl<T> SimulationBuilder<T> {
     }
     pub fn set_gravity(mut self, value: Acceleration) -> Result<Self> {
         if value.is_sign_negative() {
-            self.builder.shooter.gravity = value;
+            self.shooter.gravity = value;
             Ok(self)
         } else {
             Err(Error::NegativeExpected(
@@ -247,11 +247,45 @@ impl<T> SimulationBuilder<T> {
             ))
         }
     }
+    pub fn set_platform_speed(mut self, value: Velocity) -> Result<Self> {
+        if value.is_sign_positive() {
+            self.shooter.velocity = value;
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected(value.get::<meter_per_second>()))
+        }
+    }
+    pub fn set_platform_heading(mut self, value: Angle) -> Result<Self> {
+        let min = Angle::new::<radian>(-2.0 * PI);
+        let max = Angle::new::<radian>(2.0 * PI);
+        if value >= min && value <= max {
+            self.shooter.heading = value;
+            Ok(self)
+        } else {
+            Err(Error::OutOfRange {
+                min: min.get::<radian>(),
+                max: max.get::<radian>(),
+            })
+        }
+    }
+    pub fn set_platform_climb(mut self, value: Angle) -> Result<Self> {
+        let min = Angle::new::<radian>(-FRAC_PI_2);
+        let max = Angle::new::<radian>(FRAC_PI_2);
+        if value >= min && value <= max {
+            self.shooter.climb = value;
+            Ok(self)
+        } else {
+            Err(Error::OutOfRange {
+                min: min.get::<radian>(),
+                max: max.get::<radian>(),
+            })
+        }
+    }
 
     // Wind
     pub fn set_wind_speed(mut self, value: Velocity) -> Result<Self> {
         if value.is_sign_positive() {
-            self.builder.wind.velocity = value;
+            self.wind.velocity = value;
             Ok(self)
         } else {
             Err(Error::PositiveExpected(value.get::<meter_per_second>()))
@@ -261,7 +295,7 @@ impl<T> SimulationBuilder<T> {
         let min = Angle::new::<radian>(-2.0 * PI);
         let max = Angle::new::<radian>(2.0 * PI);
         if value >= min && value <= max {
-            self.builder.wind.yaw = value;
+            self.wind.yaw = value;
             Ok(self)
         } else {
             Err(Error::OutOfRange {
@@ -270,6 +304,150 @@ impl<T> SimulationBuilder<T> {
             })
         }
     }
+}
+impl<T> Default for SimulationBuilder<T>
+where
+    T: From<ProjectileImpl>,
+{
+    fn default() -> Self {
+        let conditions = Conditions::default();
+        Self {
+            builder: Simulation {
+                flags: Flags {
+                    coriolis: true,
+                    drag: true,
+                    gravity: true,
+                },
+                projectile: From::from(ProjectileImpl {
+                    caliber: Length::new::<inch>(0.264),
+                    weight: Mass::new::<grain>(140.0),
+                    bc: 0.305,
+                    velocity: Velocity::new::<foot_per_second>(2710.0),
+                }),
+                scope: Scope {
+                    yaw: Angle::new::<radian>(0.0),
+                    pitch: Angle::new::<radian>(0.0),
+                    roll: Angle::new::<radian>(0.0),
+                    height: Length::new::<inch>(1.5),
+                    offset: Length::new::<inch>(0.0),
+                    elevation: Turret {
+                        click: Angle::new::<moa>(0.25),
+                        scale: 1.0,
+                        travel: None,
+                    },
+                    windage: Turret {
+                        click: Angle::new::<moa>(0.25),
+                        scale: 1.0,
+                        travel: None,
+                    },
+                },
+                atmosphere: conditions.atmosphere,
+                wind: conditions.wind,
+                shooter: conditions.shooter,
+                time_step: Time::new::<second>(0.000_001),
+            },
+        }
+    }
+}
+
+impl<T> SimulationBuilder<T>
+where
+    T: From<ProjectileImpl>,
+{
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+impl<T> SimulationBuilder<T> {
+    // Create simulation with conditions used to find muzzle_pitch for 'zeroing'
+    // Starting from flat fire pitch (0.0)
+    pub fn init(self) -> Simulation<T> {
+        From::from(self)
+    }
+    pub fn set_time_step(mut self, value: Time) -> Result<Self> {
+        let min = Time::new::<second>(0.0);
+        let max = Time::new::<second>(0.1);
+        if value > min && value <= max {
+            self.builder.time_step = value;
+            Ok(self)
+        } else {
+            Err(Error::OutOfRange {
+                min: min.get::<second>(),
+                max: max.get::<second>(),
+            })
+        }
+    }
+
+    // Flags
+    pub fn use_coriolis(mut self, value: bool) -> Self {
+        self.builder.flags.coriolis = value;
+        self
+    }
+    pub fn use_drag(mut self, value: bool) -> Self {
+        self.builder.flags.drag = value;
+        self
+    }
+    pub fn use_gravity(mut self, value: bool) -> Self {
+        self.builder.flags.gravity = value;
+        self
+    }
+
+    // Conditions already validated, ie, taken from another simulation or built on their own
+    pub fn set_conditions(mut self, value: Conditions) -> Self {
+        self.builder.set_conditions(value);
+        self
+    }
+    // Same checks as setting them on Conditions directly
+    fn update_conditions<F>(mut self, update: F) -> Result<Self>
+    where
+        F: FnOnce(Conditions) -> Result<Conditions>,
+    {
+        let conditions = update(self.builder.conditions())?;
+        self.builder.set_conditions(conditions);
+        Ok(self)
+    }
+
+    // Atmosphere, wind and shooter
+
+    pub fn set_temperature(self, value: ThermodynamicTemperature) -> Result<Self> {
+        self.update_conditions(|conditions| conditions.set_temperature(value))
+    }
+    pub fn set_pressure(self, value: Pressure) -> Result<Self> {
+        self.update_conditions(|conditions| conditions.set_pressure(value))
+    }
+    pub fn set_humidity(self, value: Numeric) -> Result<Self> {
+        self.update_conditions(|conditions| conditions.set_humidity(value))
+    }
+    pub fn set_shot_angle(self, value: Angle) -> Result<Self> {
+        self.update_conditions(|conditions| conditions.set_shot_angle(value))
+    }
+    pub fn set_cant(self, value: Angle) -> Result<Self> {
+        self.update_conditions(|conditions| conditions.set_cant(value))
+    }
+    pub fn set_lattitude(self, value: Angle) -> Result<Self> {
+        self.update_conditions(|conditions| conditions.set_lattitude(value))
+    }
+    pub fn set_bearing(self, value: Angle) -> Result<Self> {
+        self.update_conditions(|conditions| conditions.set_bearing(value))
+    }
+    pub fn set_gravity(self, value: Acceleration) -> Result<Self> {
+        self.update_conditions(|conditions| conditions.set_gravity(value))
+    }
+    pub fn set_platform_speed(self, value: Velocity) -> Result<Self> {
+        self.update_conditions(|conditions| conditions.set_platform_speed(value))
+    }
+    pub fn set_platform_heading(self, value: Angle) -> Result<Self> {
+        self.update_conditions(|conditions| conditions.set_platform_heading(value))
+    }
+    pub fn set_platform_climb(self, value: Angle) -> Result<Self> {
+        self.update_conditions(|conditions| conditions.set_platform_climb(value))
+    }
+    pub fn set_wind_speed(self, value: Velocity) -> Result<Self> {
+        self.update_conditions(|conditions| conditions.set_wind_speed(value))
+    }
+    pub fn set_wind_angle(self, value: Angle) -> Result<Self> {
+        self.update_conditions(|conditions| conditions.set_wind_angle(value))
+    }
 
     //Scope
     pub fn set_scope_height(mut self, value: Length) -> Self {
@@ -292,6 +470,54 @@ impl<T> SimulationBuilder<T> {
         self.builder.scope.roll = value;
         self
     }
+    pub fn set_elevation_click(mut self, value: Angle) -> Result<Self> {
+        if value > Angle::new::<radian>(0.0) {
+            self.builder.scope.elevation.click = value;
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected(value.get::<radian>()))
+        }
+    }
+    pub fn set_elevation_scale(mut self, value: Numeric) -> Result<Self> {
+        if value > 0.0 {
+            self.builder.scope.elevation.scale = value;
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected(value))
+        }
+    }
+    pub fn set_elevation_travel(mut self, value: Angle) -> Result<Self> {
+        if value > Angle::new::<radian>(0.0) {
+            self.builder.scope.elevation.travel = Some(value);
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected(value.get::<radian>()))
+        }
+    }
+    pub fn set_windage_click(mut self, value: Angle) -> Result<Self> {
+        if value > Angle::new::<radian>(0.0) {
+            self.builder.scope.windage.click = value;
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected(value.get::<radian>()))
+        }
+    }
+    pub fn set_windage_scale(mut self, value: Numeric) -> Result<Self> {
+        if value > 0.0 {
+            self.builder.scope.windage.scale = value;
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected(value))
+        }
+    }
+    pub fn set_windage_travel(mut self, value: Angle) -> Result<Self> {
+        if value > Angle::new::<radian>(0.0) {
+            self.builder.scope.windage.travel = Some(value);
+            Ok(self)
+        } else {
+            Err(Error::PositiveExpected(value.get::<radian>()))
+        }
+    }
 }
 impl<T> SimulationBuilder<T>
 where
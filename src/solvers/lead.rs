@@ -0,0 +1,92 @@
+use crate::{
+    error::{Error, Result},
+    output::{Measurements, Packet},
+    projectiles::Projectile,
+    simulation::Simulation,
+    units::{meter, ratio, Angle, Length, Time, Velocity},
+    Numeric,
+};
+
+// Target moving at constant speed, in a straight line level with line of sight
+// Heading is relative to line of fire - 0 is directly away, 90 is crossing left to right
+#[derive(Debug, Clone, Copy)]
+pub struct Mover {
+    distance: Length, // Distance to target when fired, along line of sight
+    speed: Velocity,  // Speed of target
+    heading: Angle,   // Direction of travel
+    length: Length,   // Length of target, nose to tail
+}
+impl Mover {
+    pub fn new(distance: Length, speed: Velocity, heading: Angle, length: Length) -> Self {
+        Self {
+            distance,
+            speed,
+            heading,
+            length,
+        }
+    }
+    // Target's travel along, and across, line of sight after the given time
+    fn travel(&self, time: Time) -> (Length, Length) {
+        let distance = self.speed * time;
+        (
+            distance * self.heading.cos().get::<ratio::ratio>(),
+            distance * self.heading.sin().get::<ratio::ratio>(),
+        )
+    }
+}
+
+// Lead needed to hit a moving target, on top of the holds for a static one
+#[derive(Debug, Clone, Copy)]
+pub struct Lead {
+    angle: Angle,       // Lead angle, positive is right
+    time: Time,         // Time of flight to intercept
+    distance: Length,   // Distance to intercept, along line of sight
+    crossrange: Length, // Target's travel across line of sight
+    lengths: Numeric,   // Lead in target lengths, as seen broadside
+}
+impl Lead {
+    pub fn angle(&self) -> Angle {
+        self.angle
+    }
+    pub fn time(&self) -> Time {
+        self.time
+    }
+    pub fn distance(&self) -> Length {
+        self.distance
+    }
+    pub fn crossrange(&self) -> Length {
+        self.crossrange
+    }
+    pub fn lengths(&self) -> Numeric {
+        self.lengths
+    }
+}
+
+impl<T> Simulation<T>
+where
+    T: Projectile,
+{
+    // Lead for a moving target, using current scope angles
+    // Time of flight and intercept are solved together in a single run - the intercept is where the
+    // projectile's distance catches up to the target's, which moves on as the projectile flies
+    pub fn find_lead(&self, target: Mover) -> Result<Lead> {
+        if target.distance <= Length::new::<meter>(0.0) {
+            return Err(Error::PositiveExpected(target.distance.get::<meter>()));
+        } else if target.length <= Length::new::<meter>(0.0) {
+            return Err(Error::PositiveExpected(target.length.get::<meter>()));
+        }
+        let packet = self.reach(target.distance, |p: &Packet<T>| {
+            let (along, _) = target.travel(p.time());
+            (p.distance() - target.distance - along).get::<meter>()
+        })?;
+
+        let (_, across) = target.travel(packet.time());
+        Ok(Lead {
+            angle: across.atan2(packet.distance()),
+            time: packet.time(),
+            distance: packet.distance(),
+            crossrange: across,
+            lengths: (across / target.length).get::<ratio::ratio>(),
+        })
+    }
+}
@@ -0,0 +1,199 @@
+use crate::{
+    error::{Error, Result},
+    events::Event,
+    my_quantity,
+    output::{Measurements, Packet},
+    projectiles::Projectile,
+    simulation::Simulation,
+    solvers::roots,
+    units::{length, meter, Angle, Length, MyQuantity},
+    Numeric,
+};
+
+// Zero distance to start searching from, and the size of the first step away from it (100m)
+const FIRST_ZERO: MyQuantity<length::Dimension> = my_quantity!(100.0);
+
+// Zero distances searched - short enough to cover the scope being above bore, and
+// longer than any practical point blank zero
+const MIN_ZERO: MyQuantity<length::Dimension> = my_quantity!(1.0);
+const MAX_ZERO: MyQuantity<length::Dimension> = my_quantity!(2000.0);
+
+// Zero which keeps trajectory within a vital zone for as long as possible
+// Distances are along line of sight, ordinate is elevation above it
+#[derive(Debug, Clone, Copy)]
+pub struct PointBlank {
+    pitch: Angle,     // Scope pitch for this zero
+    yaw: Angle,       // Scope yaw for this zero
+    zero: Length,     // Zero range, ie, far line of sight crossing
+    near: Length,     // Near line of sight crossing, on the way up
+    apex: Length,     // Distance of maximum ordinate
+    ordinate: Length, // Maximum ordinate, top of the vital zone
+    range: Length,    // Maximum point blank range, where trajectory drops out the bottom
+}
+impl PointBlank {
+    pub fn pitch(&self) -> Angle {
+        self.pitch
+    }
+    pub fn yaw(&self) -> Angle {
+        self.yaw
+    }
+    pub fn zero(&self) -> Length {
+        self.zero
+    }
+    pub fn near(&self) -> Length {
+        self.near
+    }
+    pub fn apex(&self) -> Length {
+        self.apex
+    }
+    pub fn ordinate(&self) -> Length {
+        self.ordinate
+    }
+    pub fn range(&self) -> Length {
+        self.range
+    }
+}
+
+// Line of sight crossings and apex of a single run
+#[derive(Debug, Clone, Copy)]
+struct Scan {
+    near: Length,
+    far: Length,
+    apex: Length,
+    ordinate: Length,
+    range: Length,
+}
+
+impl<T> Simulation<T>
+where
+    T: Projectile,
+{
+    // Run with current scope angles until trajectory drops below the vital zone
+    // Events give the crossings and apex, then a second run finds where it leaves the zone
+    // None if it never rises through line of sight, or stops moving first
+    fn scan(&self, radius: Length) -> Option<Scan> {
+        let bottom = |p: &Packet<T>| (p.elevation() + radius).get::<meter>();
+
+        let (mut near, mut apex) = (None, None);
+        let mut far = None;
+        for event in self.iter().events() {
+            match (event, near, apex) {
+                (Event::NearZero(packet), _, _) => near = Some(packet),
+                (Event::Apex(packet), _, _) => apex = Some(packet),
+                (Event::FarZero(packet), Some(_), Some(_)) => {
+                    far = Some(packet);
+                    break;
+                }
+                (Event::FarZero(_), _, _) => (),
+            }
+        }
+        let (near, apex, far) = (near?, apex?, far?);
+        let range = self
+            .iter()
+            .crossings(bottom)
+            .find(|p| p.distance() > far.distance())?;
+        Some(Scan {
+            near: near.distance(),
+            far: far.distance(),
+            apex: apex.distance(),
+            ordinate: apex.elevation(),
+            range: range.distance(),
+        })
+    }
+    // Maximum point blank range for a vital zone of the given size (diameter)
+    // Searches zero distance, zeroing at each, until the maximum ordinate is at the top of the zone
+    // Trajectory then stays within the zone from the muzzle out to the range found, as long as
+    // the scope sits less than half the zone above the bore.
+    // Scope is left zeroed at the distance found.
+    pub fn find_point_blank(
+        &mut self,
+        vital_zone: Length,
+        tolerance: Length,
+    ) -> Result<PointBlank> {
+        if vital_zone <= Length::new::<meter>(0.0) {
+            return Err(Error::PositiveExpected(vital_zone.get::<meter>()));
+        }
+        let radius = vital_zone / 2.0;
+        let mut count = 0;
+        let mut last = None;
+        let mut ordinate = |simulation: &mut Self, distance: Numeric| {
+            count += 1;
+            let zero = Length::new::<meter>(distance);
+            let (pitch, yaw) = simulation.find_zero_angles(
+                zero,
+                Length::new::<meter>(0.0),
+                Length::new::<meter>(0.0),
+                tolerance,
+            )?;
+            let scan =
+                simulation
+                    .scan(radius)
+                    .ok_or(Error::TerminalVelocity { count, pitch, yaw })?;
+            last = Some(scan);
+            Ok((scan.ordinate - radius).get::<meter>())
+        };
+
+        // Ordinate already above the zone means a shorter zero, so step back towards the muzzle
+        // Ordinate rises again once line of sight no longer reaches the trajectory, so go carefully
+        let x0 = FIRST_ZERO.get::<meter>();
+        let f0 = ordinate(self, x0)?;
+        let step = if f0 > 0.0 { -x0 / 10.0 } else { x0 };
+        roots::solve(
+            |distance| ordinate(self, distance),
+            x0,
+            f0,
+            step,
+            MIN_ZERO.get::<meter>(),
+            MAX_ZERO.get::<meter>(),
+            tolerance.get::<meter>(),
+        )?;
+        // Root found is always the last zero ran, so scope is already there
+        let scan = last.expect("at least one scan");
+        Ok(PointBlank {
+            pitch: self.scope.pitch,
+            yaw: self.scope.yaw,
+            zero: scan.far,
+            near: scan.near,
+            apex: scan.apex,
+            ordinate: scan.ordinate,
+            range: scan.range,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        projectiles::G7,
+        simulation::SimulationBuilder,
+        units::{inch, second, yard, Time},
+    };
+
+    // Coarser time step than the default, as each search runs many zeros
+    fn point_blank(vital_zone: Length) -> PointBlank {
+        let mut simulation: Simulation<G7> = SimulationBuilder::new()
+            .set_time_step(Time::new::<second>(0.0001))
+            .unwrap()
+            .init();
+        simulation
+            .find_point_blank(vital_zone, Length::new::<inch>(0.001))
+            .unwrap()
+    }
+
+    #[test]
+    fn large_vital_zone_zeroes_past_first_zero() {
+        let found = point_blank(Length::new::<inch>(8.0));
+        assert!(found.zero() > FIRST_ZERO);
+        assert!((found.ordinate() - Length::new::<inch>(4.0)).abs() < Length::new::<inch>(0.01));
+        assert!(found.near() < found.apex() && found.apex() < found.zero());
+        assert!(found.zero() < found.range());
+    }
+    #[test]
+    fn small_vital_zone_zeroes_short_of_first_zero() {
+        let found = point_blank(Length::new::<inch>(0.25));
+        assert!(found.zero() < FIRST_ZERO);
+        assert!(found.zero() > Length::new::<yard>(10.0));
+        assert!((found.ordinate() - Length::new::<inch>(0.125)).abs() < Length::new::<inch>(0.01));
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{
+    consts::FRAC_PI_2,
+    error::{Error, Result},
+    my_quantity,
+    output::{Measurements, Packet},
+    projectiles::Projectile,
+    simulation::Simulation,
+    solvers::roots,
+    units::{angle, meter, meter_per_second, radian, Angle, Length, MyQuantity, Time},
+    vectors::Vectors,
+    Numeric,
+};
+
+// Launch angles are searched between level and straight up, relative to the horizon
+const DEG_90: MyQuantity<angle::Dimension> = my_quantity!(FRAC_PI_2);
+
+// Longest flight over level ground, and the pitch needed for it
+#[derive(Debug, Clone, Copy)]
+pub struct MaxRange {
+    pitch: Angle,  // Scope pitch found, relative to line of sight
+    range: Length, // Ground range where projectile returns to launch height
+    time: Time,    // Time of flight to that point
+    apex: Length,  // Highest point reached, above launch height
+    count: u64,    // Number of simulations ran
+}
+impl MaxRange {
+    pub fn pitch(&self) -> Angle {
+        self.pitch
+    }
+    pub fn range(&self) -> Length {
+        self.range
+    }
+    pub fn time(&self) -> Time {
+        self.time
+    }
+    pub fn apex(&self) -> Length {
+        self.apex
+    }
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+// Where a single run came back down to launch height
+#[derive(Debug, Clone, Copy)]
+struct Landing {
+    range: Length,
+    time: Time,
+    apex: Length,
+}
+
+impl<T> Simulation<T>
+where
+    T: Projectile,
+{
+    // Run once with current scope angles, until the projectile falls back through launch height
+    // Ground is taken as level with the horizon at the muzzle, range is measured along that plane
+    // None means the projectile stopped moving before landing
+    fn landing(&self) -> Option<Landing> {
+        let vertical = |p: &Packet<T>| p.velocity.get_y().get::<meter_per_second>();
+        let mut iter = self.iter();
+        let first = iter.next()?;
+        let launch = first.height();
+        let residual = |p: &Packet<T>| (p.height() - launch).get::<meter>();
+
+        let mut apex = launch;
+        let mut prev = first;
+        for packet in iter {
+            if vertical(&prev) >= 0.0 && vertical(&packet) < 0.0 {
+                apex = prev.crossing(&packet, &vertical).height();
+            }
+            if residual(&packet) < 0.0 && vertical(&packet) < 0.0 {
+                let p = prev.crossing(&packet, &residual);
+                let (x, z) = (
+                    p.position.get_x() - first.position.get_x(),
+                    p.position.get_z() - first.position.get_z(),
+                );
+                return Some(Landing {
+                    range: (x * x + z * z).sqrt(),
+                    time: p.time(),
+                    apex: apex - launch,
+                });
+            }
+            prev = packet;
+        }
+        None
+    }
+    // Search pitch for the longest flight over level ground, under current conditions
+    // With drag this is usually well short of 45 degrees, and flatter for lighter, slower projectiles
+    // Range is flat near the best angle, so tolerance on pitch need not be very fine.
+    // Scope is left at the pitch found.
+    pub fn find_max_range(&mut self, tolerance: Angle) -> Result<MaxRange> {
+        let los = self.shooter.pitch;
+        let mut last = None;
+        let peak = roots::maximize(
+            |pitch| {
+                self.scope.pitch = Angle::new::<radian>(pitch);
+                let landing = self.landing();
+                last = Some((pitch, landing));
+                Ok(landing.map_or(Numeric::NEG_INFINITY, |l| l.range.get::<meter>()))
+            },
+            -los.get::<radian>(),
+            (DEG_90 - los).get::<radian>(),
+            Numeric::INFINITY,
+            tolerance.get::<radian>(),
+        )?;
+        self.scope.pitch = Angle::new::<radian>(peak.x);
+        let mut count = peak.count;
+        // Search usually ends on the peak, otherwise run it again
+        let landing = match last {
+            Some((pitch, landing)) if pitch == peak.x => landing,
+            _ => {
+                count += 1;
+                self.landing()
+            }
+        };
+        let landing = landing.ok_or(Error::TerminalVelocity {
+            count,
+            pitch: self.scope.pitch,
+            yaw: self.scope.yaw,
+        })?;
+        Ok(MaxRange {
+            pitch: self.scope.pitch,
+            range: landing.range,
+            time: landing.time,
+            apex: landing.apex,
+            count,
+        })
+    }
+}
@@ -0,0 +1,287 @@
+use crate::{
+    error::{Error, Result},
+    Numeric,
+};
+
+// Bracket width at which a root is accepted, even if the residual is still outside tolerance
+// Reached when the residual jumps across zero, ie, from a step function or lost precision
+const EPSILON: Numeric = 1e-12;
+
+// Upper bound on evaluations for a single solve - bisection alone converges well before this
+const MAX_ITERATIONS: u64 = 200;
+
+// Root of a residual, along with evaluations used to get there
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Root {
+    pub(crate) x: Numeric,
+    pub(crate) count: u64,
+}
+
+// Find a root by walking from x0 (with residual f0 already known) until the residual changes
+// sign, growing the step each time, then narrowing that bracket
+// Search never leaves [min, max] - if there is no change of sign within, this fails
+pub(crate) fn solve<F>(
+    mut f: F,
+    x0: Numeric,
+    f0: Numeric,
+    step: Numeric,
+    min: Numeric,
+    max: Numeric,
+    tolerance: Numeric,
+) -> Result<Root>
+where
+    F: FnMut(Numeric) -> Result<Numeric>,
+{
+    if f0.abs() <= tolerance {
+        return Ok(Root { x: x0, count: 0 });
+    }
+    let (mut a, mut fa) = (x0, f0);
+    let mut step = if step == 0.0 { EPSILON } else { step };
+    let mut count = 0;
+    loop {
+        let b = (a + step).max(min).min(max);
+        let fb = f(b)?;
+        count += 1;
+        if fb.abs() <= tolerance {
+            return Ok(Root { x: b, count });
+        } else if (fa < 0.0) != (fb < 0.0) {
+            let root = between(f, (a, fa), (b, fb), tolerance)?;
+            return Ok(Root {
+                count: count + root.count,
+                ..root
+            });
+        } else if b <= min || b >= max || count >= MAX_ITERATIONS {
+            return Err(Error::NoBracket { count, min, max });
+        }
+        // Secant through the last two points usually lands just short of the root, so go twice as far
+        // Never more than doubling though, as the residual may be far from linear
+        // Infinite residuals (ie, fell short) give no usable secant, so just double - same as NaN
+        let secant = -fb * (b - a) / (fb - fa);
+        step = if fa.is_finite()
+            && fb.is_finite()
+            && secant.is_finite()
+            && secant != 0.0
+            && secant.signum() == step.signum()
+        {
+            2.0 * secant.abs().min(step.abs()).max(EPSILON) * step.signum()
+        } else {
+            2.0 * step
+        };
+        a = b;
+        fa = fb;
+    }
+}
+
+// Narrow a bracket known to contain a change of sign, using false position
+// The Illinois modification halves the retained end's residual when the same end is kept twice,
+// which avoids the one-sided stalls of plain false position
+// Ends with infinite residuals (ie, projectile never arrived) fall back to bisection, as does keeping
+// the same end three times running - halving can take many steps to tame a huge residual
+pub(crate) fn between<F>(
+    mut f: F,
+    (mut a, mut fa): (Numeric, Numeric),
+    (mut b, mut fb): (Numeric, Numeric),
+    tolerance: Numeric,
+) -> Result<Root>
+where
+    F: FnMut(Numeric) -> Result<Numeric>,
+{
+    let mut side = 0;
+    let mut repeats = 0;
+    let mut count = 0;
+    loop {
+        let x = if fa.is_finite() && fb.is_finite() && repeats < 3 {
+            (a * fb - b * fa) / (fb - fa)
+        } else {
+            (a + b) / 2.0
+        };
+        let fx = f(x)?;
+        count += 1;
+        if fx.abs() <= tolerance || (b - a).abs() <= EPSILON {
+            return Ok(Root { x, count });
+        } else if count >= MAX_ITERATIONS {
+            return Err(Error::IterationLimit {
+                count,
+                value: x,
+                residual: fx,
+            });
+        }
+        let next = if (fx < 0.0) == (fa < 0.0) { -1 } else { 1 };
+        repeats = if next == side { repeats + 1 } else { 1 };
+        if next == -1 {
+            a = x;
+            fa = fx;
+            if side == -1 {
+                fb /= 2.0;
+            }
+        } else {
+            b = x;
+            fb = fx;
+            if side == 1 {
+                fa /= 2.0;
+            }
+        }
+        side = next;
+    }
+}
+
+// Highest point found on a residual, along with evaluations used to get there
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Peak {
+    pub(crate) x: Numeric,
+    pub(crate) fx: Numeric,
+    pub(crate) count: u64,
+}
+
+// Golden section search for the maximum of a residual with a single peak in [min, max]
+// Stops early as soon as the residual exceeds threshold, for callers that only need some point
+// above it, otherwise narrows until the interval is within tolerance
+pub(crate) fn maximize<F>(
+    mut f: F,
+    min: Numeric,
+    max: Numeric,
+    threshold: Numeric,
+    tolerance: Numeric,
+) -> Result<Peak>
+where
+    F: FnMut(Numeric) -> Result<Numeric>,
+{
+    let ratio = (Numeric::sqrt(5.0) - 1.0) / 2.0;
+    let (mut a, mut b) = (min, max);
+    let mut c = b - (b - a) * ratio;
+    let mut fc = f(c)?;
+    if fc > threshold {
+        return Ok(Peak {
+            x: c,
+            fx: fc,
+            count: 1,
+        });
+    }
+    let mut d = a + (b - a) * ratio;
+    let mut fd = f(d)?;
+    let mut count = 2;
+    while fd <= threshold && (b - a).abs() > tolerance && count < MAX_ITERATIONS {
+        // Keep whichever side holds the higher interior point - the other point carries over
+        if fc > fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - (b - a) * ratio;
+            fc = f(c)?;
+            if fc > threshold {
+                return Ok(Peak {
+                    x: c,
+                    fx: fc,
+                    count: count + 1,
+                });
+            }
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + (b - a) * ratio;
+            fd = f(d)?;
+        }
+        count += 1;
+    }
+    if fc > fd {
+        Ok(Peak {
+            x: c,
+            fx: fc,
+            count,
+        })
+    } else {
+        Ok(Peak {
+            x: d,
+            fx: fd,
+            count,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Short of 1, the residual is treated as infinitely low, as when a projectile never arrives
+    fn fell_short(x: Numeric) -> Result<Numeric> {
+        Ok(if x < 1.0 {
+            Numeric::NEG_INFINITY
+        } else {
+            x - 3.0
+        })
+    }
+
+    #[test]
+    fn solve_walks_to_root() {
+        let root = solve(|x| Ok(x * x - 2.0), 0.0, -2.0, 0.1, 0.0, 10.0, 1e-12).unwrap();
+        assert!((root.x - Numeric::sqrt(2.0)).abs() < 1e-9);
+    }
+    #[test]
+    fn solve_starting_at_root() {
+        let root = solve(|_| unreachable!(), 1.0, 0.0, 0.1, 0.0, 10.0, 1e-12).unwrap();
+        assert_eq!((root.x, root.count), (1.0, 0));
+    }
+    #[test]
+    fn solve_from_infinite_residual() {
+        let root = solve(
+            fell_short,
+            0.0,
+            Numeric::NEG_INFINITY,
+            0.5,
+            0.0,
+            10.0,
+            1e-12,
+        )
+        .unwrap();
+        assert!((root.x - 3.0).abs() < 1e-9);
+        assert!(root.count < 50);
+    }
+    #[test]
+    fn solve_without_bracket() {
+        let err = solve(|x| Ok(x + 1.0), 0.0, 1.0, 0.1, 0.0, 10.0, 1e-12).unwrap_err();
+        assert!(matches!(err, Error::NoBracket { .. }));
+    }
+    #[test]
+    fn between_finite() {
+        let root = between(|x| Ok(x * x * x - 8.0), (0.0, -8.0), (5.0, 117.0), 1e-12).unwrap();
+        assert!((root.x - 2.0).abs() < 1e-9);
+    }
+    #[test]
+    fn between_infinite_end() {
+        let root = between(fell_short, (0.0, Numeric::NEG_INFINITY), (10.0, 7.0), 1e-12).unwrap();
+        assert!((root.x - 3.0).abs() < 1e-9);
+    }
+    #[test]
+    fn between_step() {
+        let f = |x: Numeric| Ok(if x < 2.0 { -1.0 } else { 1.0 });
+        let root = between(f, (0.0, -1.0), (5.0, 1.0), 1e-12).unwrap();
+        assert!((root.x - 2.0).abs() < 1e-9);
+    }
+    #[test]
+    fn maximize_peak() {
+        let f = |x: Numeric| Ok(-(x - 2.0) * (x - 2.0));
+        let peak = maximize(f, 0.0, 5.0, Numeric::INFINITY, 1e-9).unwrap();
+        assert!((peak.x - 2.0).abs() < 1e-6);
+        assert!(peak.fx <= 0.0);
+    }
+    #[test]
+    fn maximize_infinite_residual() {
+        let f = |x: Numeric| {
+            Ok(if x > 3.0 {
+                Numeric::NEG_INFINITY
+            } else {
+                -(x - 2.0) * (x - 2.0)
+            })
+        };
+        let peak = maximize(f, 0.0, 5.0, Numeric::INFINITY, 1e-9).unwrap();
+        assert!((peak.x - 2.0).abs() < 1e-6);
+    }
+    #[test]
+    fn maximize_threshold() {
+        let f = |x: Numeric| Ok(-(x - 2.0) * (x - 2.0));
+        let peak = maximize(f, 0.0, 5.0, -10.0, 1e-9).unwrap();
+        assert_eq!(peak.count, 1);
+        assert!(peak.fx > -10.0);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{
+    error::{Error, Result},
+    output::{Measurements, Packet, Sample},
+    projectiles::Projectile,
+    simulation::{Simulation, SimulationBuilder},
+    units::{meter, Angle, Length},
+};
+
+// Target as observed from the firing position, ie, from a laser rangefinder
+#[derive(Debug, Clone, Copy)]
+pub struct Observation {
+    range: Length,      // Slant range, along line of sight
+    inclination: Angle, // Line of sight angle above (or below) the horizon
+    cant: Angle,        // Roll of the rifle about line of sight
+}
+impl Observation {
+    pub fn new(range: Length, inclination: Angle, cant: Angle) -> Self {
+        Self {
+            range,
+            inclination,
+            cant,
+        }
+    }
+    pub fn range(&self) -> Length {
+        self.range
+    }
+    pub fn inclination(&self) -> Angle {
+        self.inclination
+    }
+    pub fn cant(&self) -> Angle {
+        self.cant
+    }
+}
+
+// Holds needed to hit an observed target, from the current zero
+// Positive is up and right, ie, hold (or dial) this much toward where the shot should go
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    elevation: Angle, // Elevation hold
+    windage: Angle,   // Windage hold
+    sample: Sample,   // State where projectile reaches the target's range
+}
+impl Solution {
+    pub fn elevation(&self) -> Angle {
+        self.elevation
+    }
+    pub fn windage(&self) -> Angle {
+        self.windage
+    }
+    pub fn sample(&self) -> &Sample {
+        &self.sample
+    }
+}
+
+impl<T> Simulation<T>
+where
+    T: Projectile + Clone,
+{
+    // Firing solution for an observed target, using the current (zeroed) scope angles
+    // Runs on a copy, with shot angle and cant taken from the observation, so the zero is untouched
+    // Holds are relative to the canted scope, the same frame the reticle is seen in
+    pub fn firing_solution(&self, observation: Observation) -> Result<Solution> {
+        if observation.range <= Length::new::<meter>(0.0) {
+            return Err(Error::PositiveExpected(observation.range.get::<meter>()));
+        }
+        let simulation = SimulationBuilder::from(self.clone())
+            .set_shot_angle(observation.inclination)?
+            .set_cant(observation.cant)?
+            .init();
+
+        let packet = simulation.reach(observation.range, |p: &Packet<T>| {
+            (p.distance() - observation.range).get::<meter>()
+        })?;
+
+        Ok(Solution {
+            elevation: -packet.elevation().atan2(packet.distance()),
+            windage: -packet.windage().atan2(packet.distance()),
+            sample: Sample::from(packet),
+        })
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{
+    consts::FRAC_PI_2,
+    error::{Error, Result},
+    my_quantity,
+    projectiles::Projectile,
+    simulation::Simulation,
+    solvers::{roots, zero::Target},
+    units::{angle, meter, meter_per_second, velocity, Angle, Length, MyQuantity, Velocity},
+    Numeric,
+};
+
+// Full value crosswind, relative to bearing - wind from the right is positive
+const DEG_90: MyQuantity<angle::Dimension> = my_quantity!(FRAC_PI_2);
+
+// Crosswind speeds searched, either direction (50m/s, ie, over 100mph)
+const MAX_CROSSWIND: MyQuantity<velocity::Dimension> = my_quantity!(50.0);
+
+// First step taken from the current crosswind (1m/s)
+const FIRST_STEP: MyQuantity<velocity::Dimension> = my_quantity!(1.0);
+
+// Effective wind found from an observed miss
+#[derive(Debug, Clone, Copy)]
+pub struct Crosswind {
+    speed: Velocity, // Wind speed
+    angle: Angle,    // Wind angle, either 90 or -90 degrees
+    count: u64,      // Number of simulations ran
+}
+impl Crosswind {
+    pub fn speed(&self) -> Velocity {
+        self.speed
+    }
+    pub fn angle(&self) -> Angle {
+        self.angle
+    }
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+impl<T> Simulation<T>
+where
+    T: Projectile,
+{
+    // Set wind to a full value crosswind, negative speed blowing from the left
+    fn set_crosswind(&mut self, speed: Velocity) {
+        if speed < Velocity::new::<meter_per_second>(0.0) {
+            self.wind.velocity = -speed;
+            self.wind.yaw = -DEG_90;
+        } else {
+            self.wind.velocity = speed;
+            self.wind.yaw = DEG_90;
+        }
+    }
+    // Full value crosswind which explains an observed horizontal miss at distance (positive is right)
+    // The miss is taken as relative to where current conditions say the shot should have gone, so
+    // current scope angles and wind are what was used for that shot.
+    // Wind is replaced by the crosswind found, ready for follow up shots.
+    pub fn find_crosswind(
+        &mut self,
+        distance: Length,
+        miss: Length,
+        tolerance: Length,
+    ) -> Result<Crosswind> {
+        let target = Target {
+            distance,
+            elevation: Length::new::<meter>(0.0),
+            windage: Length::new::<meter>(0.0),
+        };
+        let mut count = 1;
+        let expected = self.shoot(target).ok_or(Error::TerminalVelocity {
+            count,
+            pitch: self.scope.pitch,
+            yaw: self.scope.yaw,
+        })?;
+        let observed = expected.windage + miss;
+
+        // Start from the crosswind part of the current wind
+        let current = self.wind.velocity * self.wind.yaw.sin();
+        let mut residual = |simulation: &mut Self, speed: Numeric| {
+            count += 1;
+            simulation.set_crosswind(Velocity::new::<meter_per_second>(speed));
+            let shot = simulation.shoot(target).ok_or(Error::TerminalVelocity {
+                count,
+                pitch: simulation.scope.pitch,
+                yaw: simulation.scope.yaw,
+            })?;
+            Ok((shot.windage - observed).get::<meter>())
+        };
+        let x0 = current.get::<meter_per_second>();
+        let f0 = residual(self, x0)?;
+        // Wind from the right pushes the shot left, so step toward more of it when landing right
+        let step = FIRST_STEP.get::<meter_per_second>() * f0.signum();
+        roots::solve(
+            |speed| residual(self, speed),
+            x0,
+            f0,
+            step,
+            -MAX_CROSSWIND.get::<meter_per_second>(),
+            MAX_CROSSWIND.get::<meter_per_second>(),
+            tolerance.get::<meter>(),
+        )?;
+        // Root found is always the last one ran, so wind is already set
+        Ok(Crosswind {
+            speed: self.wind.velocity,
+            angle: self.wind.yaw,
+            count,
+        })
+    }
+}
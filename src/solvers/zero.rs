@@ -2,12 +2,13 @@ use crate::{
     consts::{FRAC_PI_2, FRAC_PI_4},
     error::{Error, Result},
     my_quantity,
-    output::Measurements,
-    output::Packet,
+    output::{Measurements, Packet},
     projectiles::Projectile,
-    simulation::Scope,
     simulation::Simulation,
-    units::{angle, radian, Angle, Length, MyQuantity},
+    solvers::roots,
+    units::{angle, degree, meter, meter_per_second, radian, Angle, Length, MyQuantity, Velocity},
+    vectors::Vectors,
+    Numeric,
 };
 
 // This angle will trace the longest possible trajectory for a projectile (45 degrees)
@@ -17,120 +18,286 @@ const DEG_45: MyQuantity<angle::Dimension> = my_quantity!(FRAC_PI_4);
 // Also should never try to pitch this low - not sure if this ever happens in practice
 const DEG_90: MyQuantity<angle::Dimension> = my_quantity!(FRAC_PI_2);
 
-struct IterFindAdjustments<'t, T, F, E, W>
-where
-    T: Projectile,
-    F: Fn(&Packet<T>) -> bool,
-    E: Fn(&Packet<T>) -> Angle,
-    W: Fn(&Packet<T>) -> Angle,
-{
-    sim: &'t mut Simulation<T>,
+// Pitch and yaw are solved one at a time, each holding the other fixed
+// They barely interact, so this almost always settles on the second pass
+const MAX_PASSES: u64 = 16;
 
-    finder: F,
-    elevation_adjuster: E,
-    windage_adjuster: W,
+// Narrowest pitch interval kept when searching for the angle of furthest reach (radians)
+// Elevation at distance is flat near that angle, so this is much finer than needed
+const PEAK_TOLERANCE: Numeric = 1e-6;
 
-    elevation_adjustment: Angle,
-    windage_adjustment: Angle,
-    count: u64,
+// Result of zeroing - scope angles, along with how they were reached
+#[derive(Debug, Clone, Copy)]
+pub struct Zero {
+    pitch: Angle,      // Scope pitch found
+    yaw: Angle,        // Scope yaw found
+    count: u64,        // Number of simulations ran
+    elevation: Length, // Elevation remaining from offset at distance
+    windage: Length,   // Windage remaining from offset at distance
+}
+impl Zero {
+    pub fn pitch(&self) -> Angle {
+        self.pitch
+    }
+    pub fn yaw(&self) -> Angle {
+        self.yaw
+    }
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    pub fn elevation(&self) -> Length {
+        self.elevation
+    }
+    pub fn windage(&self) -> Length {
+        self.windage
+    }
 }
 
-// This never returns None - it returns Some(Result) which can indicate failure instead
-// This is just to capture reason why iteration stopped
-impl<T, F, E, W> Iterator for IterFindAdjustments<'_, T, F, E, W>
-where
-    T: Projectile,
-    F: Fn(&Packet<T>) -> bool,
-    E: Fn(&Packet<T>) -> Angle,
-    W: Fn(&Packet<T>) -> Angle,
-{
-    type Item = Result<(Angle, Angle, Length, Length)>;
-    fn next(&mut self) -> Option<Self::Item> {
-        // Previous pitch/yaw values to ensure angles are changing
-        let &mut Self {
-            sim:
-                &mut Simulation {
-                    scope:
-                        Scope {
-                            pitch: prev_pitch,
-                            yaw: prev_yaw,
-                            ..
-                        },
-                    ..
-                },
-            ..
-        } = self;
+// Both pitch solutions for a target - direct fire below the angle of furthest reach, indirect above
+// Only the direct solution exists when the target is right at the limit of reach
+#[derive(Debug, Clone, Copy)]
+pub struct Solutions {
+    direct: Zero,
+    indirect: Option<Zero>,
+}
+impl Solutions {
+    pub fn direct(&self) -> Zero {
+        self.direct
+    }
+    pub fn indirect(&self) -> Option<Zero> {
+        self.indirect
+    }
+}
 
-        // Capture current adjustments/count for use during checks later
-        // Cannot return pitch/yaw here, as zeroth iteration doesn't have
-        // meaningful value for elevation/windage - really have to run iter at least once
-        self.count += 1;
-        self.sim.scope.pitch += self.elevation_adjustment;
-        self.sim.scope.yaw += self.windage_adjustment;
-        let count = self.count;
-        let pitch = self.sim.scope.pitch;
-        let yaw = self.sim.scope.yaw;
+// Point to hit, relative to line of sight
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Target {
+    pub(crate) distance: Length,
+    pub(crate) elevation: Length,
+    pub(crate) windage: Length,
+}
 
-        // Ensure angle is changing from previous value - may not for really small floats
-        if true
-            && pitch == prev_pitch
-            && yaw == prev_yaw
-            // Ignore first time, since both should be still be 0.0 at this point
-            && count != 1
-        {
-            Some(Err(Error::AngleNotChanging { count, pitch, yaw }))
-        } else if (pitch >= DEG_45 || pitch <= -DEG_90) || (yaw >= DEG_90 || yaw <= -DEG_90) {
-            Some(Err(Error::AngleRange { count, pitch, yaw }))
-        } else if let Some(packet) = self.sim.into_iter().fuse().find(&self.finder) {
-            self.elevation_adjustment = (self.elevation_adjuster)(&packet);
-            self.windage_adjustment = (self.windage_adjuster)(&packet);
-            let elevation = packet.elevation();
-            let windage = packet.windage();
-            Some(Ok((pitch, yaw, elevation, windage)))
-        } else {
-            Some(Err(Error::TerminalVelocity { count, pitch, yaw }))
+// Where a single run reached the target distance
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Shot {
+    pub(crate) elevation: Length, // Elevation from target
+    pub(crate) windage: Length,   // Windage from target
+    pub(crate) vertical: Angle,   // Estimated pitch adjustment toward target
+    pub(crate) horizontal: Angle, // Estimated yaw adjustment toward target
+}
+impl Shot {
+    fn new(target: Target, elevation: Length, windage: Length) -> Self {
+        Self {
+            elevation: elevation - target.elevation,
+            windage: windage - target.windage,
+            vertical: target.elevation.atan2(target.distance) - elevation.atan2(target.distance),
+            horizontal: target.windage.atan2(target.distance) - windage.atan2(target.distance),
         }
     }
 }
 
-impl<'t, T> Simulation<T>
+impl<T> Simulation<T>
 where
     T: Projectile,
 {
-    fn find_adjustments<F, E, W>(
-        &'t mut self,
-        finder: F,
-        elevation_adjuster: E,
-        windage_adjuster: W,
-    ) -> IterFindAdjustments<'t, T, F, E, W>
-    where
-        F: Fn(&Packet<T>) -> bool,
-        E: Fn(&Packet<T>) -> Angle,
-        W: Fn(&Packet<T>) -> Angle,
-    {
-        IterFindAdjustments {
-            sim: self,
+    // Run once with current scope angles, interpolating exactly at distance
+    // Stops early once projectile is below the target and falling steeper than 45 degrees (relative to
+    // line of sight), since it can never come back up, and may take forever to creep out to distance.
+    // The rest of the way is then taken as a straight line along the last velocity, which is always
+    // above the real path, so the result is still low.
+    // None means the projectile stopped moving toward the distance altogether
+    pub(crate) fn shoot(&self, target: Target) -> Option<Shot> {
+        let residual = |p: &Packet<T>| (p.distance() - target.distance).get::<meter>();
+        let mut iter = self.iter().until(move |p| p.plunging(target.elevation));
+        let mut prev = iter.next()?;
+        if residual(&prev) >= 0.0 {
+            return Some(Shot::new(target, prev.elevation(), prev.windage()));
+        }
+        for packet in iter {
+            if residual(&packet) >= 0.0 {
+                let p = prev.crossing(&packet, &residual);
+                return Some(Shot::new(target, p.elevation(), p.windage()));
+            }
+            prev = packet;
+        }
+        let velocity = prev.relative_velocity();
+        if velocity.get_x() > Velocity::new::<meter_per_second>(0.0) {
+            let time = (target.distance - prev.distance()) / velocity.get_x();
+            Some(Shot::new(
+                target,
+                prev.elevation() + velocity.get_y() * time,
+                prev.windage() + velocity.get_z() * time,
+            ))
+        } else {
+            None
+        }
+    }
+    // Solve for pitch within bounds, holding yaw
+    // Falling short of the distance counts as infinitely low, so bracketing still works
+    // Slope is the sign of elevation change with pitch - negative above the angle of furthest reach,
+    // where the estimated adjustment points the wrong way
+    // Returns the shot at the pitch found, as the last one ran
+    pub(crate) fn zero_pitch(
+        &mut self,
+        shot: Option<Shot>,
+        target: Target,
+        (min, max): (Angle, Angle),
+        slope: Numeric,
+        tolerance: Length,
+    ) -> Result<(roots::Root, Option<Shot>)> {
+        let f0 = shot.map_or(Numeric::NEG_INFINITY, |s| s.elevation.get::<meter>());
+        let step = shot.map_or(Angle::new::<degree>(1.0), |s| s.vertical) * slope;
+        let x0 = self.scope.pitch.get::<radian>();
+        let mut last = shot;
+        let root = roots::solve(
+            |pitch| {
+                self.scope.pitch = Angle::new::<radian>(pitch);
+                last = self.shoot(target);
+                Ok(last.map_or(Numeric::NEG_INFINITY, |s| s.elevation.get::<meter>()))
+            },
+            x0,
+            f0,
+            step.get::<radian>(),
+            min.get::<radian>(),
+            max.get::<radian>(),
+            tolerance.get::<meter>(),
+        )?;
+        Ok((root, last))
+    }
+    // Solve for yaw, holding pitch - pitch should already reach distance, so failing to is an error
+    // Returns the shot at the yaw found, as the last one ran
+    pub(crate) fn zero_yaw(
+        &mut self,
+        shot: Shot,
+        target: Target,
+        tolerance: Length,
+    ) -> Result<(roots::Root, Shot)> {
+        let x0 = self.scope.yaw.get::<radian>();
+        let mut last = shot;
+        let mut count = 0;
+        let root = roots::solve(
+            |yaw| {
+                count += 1;
+                self.scope.yaw = Angle::new::<radian>(yaw);
+                last = self.shoot(target).ok_or(Error::TerminalVelocity {
+                    count,
+                    pitch: self.scope.pitch,
+                    yaw: self.scope.yaw,
+                })?;
+                Ok(last.windage.get::<meter>())
+            },
+            x0,
+            shot.windage.get::<meter>(),
+            shot.horizontal.get::<radian>(),
+            -DEG_90.get::<radian>(),
+            DEG_90.get::<radian>(),
+            tolerance.get::<meter>(),
+        )?;
+        Ok((root, last))
+    }
+    // Searching off the end of the angle bounds means the zero would need angles outside them
+    // Scope is left at the last angles tried
+    fn angle_range(&self, err: Error, count: u64) -> Error {
+        match err {
+            Error::NoBracket { count: tried, .. } => Error::AngleRange {
+                count: count + tried,
+                pitch: self.scope.pitch,
+                yaw: self.scope.yaw,
+            },
+            err => err,
+        }
+    }
+    // Alternate between pitch and yaw, starting from the given shot at current scope angles
+    pub(crate) fn zero_within(
+        &mut self,
+        mut shot: Option<Shot>,
+        target: Target,
+        bounds: (Angle, Angle),
+        slope: Numeric,
+        tolerance: Length,
+    ) -> Result<Zero> {
+        let mut count = 0;
+        let within =
+            |shot: &Shot| shot.elevation.abs() <= tolerance && shot.windage.abs() <= tolerance;
+        for _ in 0..MAX_PASSES {
+            let (root, pitched) = self
+                .zero_pitch(shot, target, bounds, slope, tolerance)
+                .map_err(|err| self.angle_range(err, count))?;
+            self.scope.pitch = Angle::new::<radian>(root.x);
+            count += root.count;
 
-            finder,
-            elevation_adjuster,
-            windage_adjuster,
+            let pitched = pitched.ok_or(Error::TerminalVelocity {
+                count,
+                pitch: self.scope.pitch,
+                yaw: self.scope.yaw,
+            })?;
+            let (root, yawed) = if within(&pitched) {
+                (
+                    roots::Root {
+                        x: self.scope.yaw.get::<radian>(),
+                        count: 0,
+                    },
+                    pitched,
+                )
+            } else {
+                self.zero_yaw(pitched, target, tolerance)
+                    .map_err(|err| self.angle_range(err, count))?
+            };
+            self.scope.yaw = Angle::new::<radian>(root.x);
+            count += root.count;
 
-            elevation_adjustment: Angle::new::<radian>(0.0),
-            windage_adjustment: Angle::new::<radian>(0.0),
-            count: 0u64,
+            if within(&yawed) {
+                return Ok(Zero {
+                    pitch: self.scope.pitch,
+                    yaw: self.scope.yaw,
+                    count,
+                    elevation: yawed.elevation,
+                    windage: yawed.windage,
+                });
+            }
+            shot = Some(yawed);
         }
+        Err(Error::IterationLimit {
+            count,
+            value: self.scope.pitch.get::<radian>(),
+            residual: shot.map_or(Numeric::NEG_INFINITY, |s| s.elevation.get::<meter>()),
+        })
     }
-}
-
-impl<T> Simulation<T>
-where
-    T: Projectile,
-{
-    // Much more practical zeroing algorithm.  Just run flat simulation, then look at moa, and adjust
-    // by that number - it's usually pretty close to the adjustment needed, so simulation only needs to be
-    // ran once for most practical inputs.  Can also handle larger ranges, and will just continue to re-adjust
-    // until tolerance is met.  Since MOA adjustment is always a positive number, this is probably broken for some inputs
-    // This should also work for windage adjustments as well
+    // Bracketing zero - starts from current scope angles, and uses the moa adjustment at distance
+    // as the first step, which is usually close enough that only a few runs are needed.
+    // Once the target is bracketed, convergence is guaranteed within the angle bounds.
+    // Scope is left at the angles found.
+    pub fn find_zero(
+        &mut self,
+        distance: Length,
+        elevation_offset: Length,
+        windage_offset: Length,
+        tolerance: Length,
+    ) -> Result<Zero> {
+        let target = Target {
+            distance,
+            elevation: elevation_offset,
+            windage: windage_offset,
+        };
+        let mut shot = self.shoot(target);
+        let mut count = 1;
+        // Pitch and yaw adjustments from the first run are usually very close, so take both at once
+        // before settling each in turn - this is only a starting point, so keep within bounds
+        if let Some(first) = shot {
+            let pitch = self.scope.pitch + first.vertical;
+            let yaw = self.scope.yaw + first.horizontal;
+            if pitch > -DEG_90 && pitch < DEG_45 && yaw > -DEG_90 && yaw < DEG_90 {
+                self.scope.pitch = pitch;
+                self.scope.yaw = yaw;
+                shot = self.shoot(target);
+                count += 1;
+            }
+        }
+        let mut zero = self.zero_within(shot, target, (-DEG_90, DEG_45), 1.0, tolerance)?;
+        zero.count += count;
+        Ok(zero)
+    }
+    // Same as find_zero, returning only the scope angles found
     pub fn find_zero_angles(
         &mut self,
         distance: Length,
@@ -138,28 +305,68 @@ where
         windage_offset: Length,
         tolerance: Length,
     ) -> Result<(Angle, Angle)> {
-        let (pitch, yaw, _, _) = self
-            .find_adjustments(
-                |p: &Packet<T>| p.distance() >= distance,
-                |p: &Packet<T>| p.offset_vertical_angle(elevation_offset, tolerance),
-                |p: &Packet<T>| p.offset_horizontal_angle(windage_offset, tolerance),
-            )
-            .find_map(|result| match result {
-                Ok((_, _, elevation, windage)) => {
-                    if true
-                        && elevation >= (elevation_offset - tolerance)
-                        && elevation <= (elevation_offset + tolerance)
-                        && windage >= (windage_offset - tolerance)
-                        && windage <= (windage_offset + tolerance)
-                    {
-                        Some(result)
-                    } else {
-                        None
-                    }
-                }
-                err @ Err(_) => Some(err),
-            })
-            .unwrap()?; // The iterator always returns Some - unwrap to inner result, then handle with "?"
-        Ok((pitch, yaw))
+        let zero = self.find_zero(distance, elevation_offset, windage_offset, tolerance)?;
+        Ok((zero.pitch, zero.yaw))
+    }
+    // Direct and indirect (lob) solutions for a target
+    // First searches pitch for any shot landing above the target, which splits the two solutions,
+    // otherwise settles on the angle of furthest reach - if that still lands low, target is out of reach.
+    // Each solution is then zeroed on its own side of that angle, and both counts include the search.
+    // Scope is left at the direct solution.
+    pub fn find_solutions(
+        &mut self,
+        distance: Length,
+        elevation_offset: Length,
+        windage_offset: Length,
+        tolerance: Length,
+    ) -> Result<Solutions> {
+        let target = Target {
+            distance,
+            elevation: elevation_offset,
+            windage: windage_offset,
+        };
+        let yaw = self.scope.yaw;
+        let mut last = None;
+        let peak = roots::maximize(
+            |pitch| {
+                self.scope.pitch = Angle::new::<radian>(pitch);
+                let shot = self.shoot(target);
+                last = Some((pitch, shot));
+                Ok(shot.map_or(Numeric::NEG_INFINITY, |s| s.elevation.get::<meter>()))
+            },
+            -DEG_90.get::<radian>(),
+            DEG_90.get::<radian>(),
+            tolerance.get::<meter>(),
+            PEAK_TOLERANCE,
+        )?;
+        let apex = Angle::new::<radian>(peak.x);
+        let mut count = peak.count;
+        if peak.fx < -tolerance.get::<meter>() {
+            return Err(Error::OutOfReach { distance });
+        }
+        // Search usually ends on the peak, otherwise run it again
+        self.scope.pitch = apex;
+        let shot = match last {
+            Some((pitch, shot)) if pitch == peak.x => shot,
+            _ => {
+                count += 1;
+                self.shoot(target)
+            }
+        };
+
+        let mut direct = self.zero_within(shot, target, (-DEG_90, apex), 1.0, tolerance)?;
+        direct.count += count;
+        let indirect = if peak.fx > tolerance.get::<meter>() {
+            self.scope.pitch = apex;
+            self.scope.yaw = yaw;
+            let mut indirect = self.zero_within(shot, target, (apex, DEG_90), -1.0, tolerance)?;
+            indirect.count += count;
+            Some(indirect)
+        } else {
+            None
+        };
+        self.scope.pitch = direct.pitch;
+        self.scope.yaw = direct.yaw;
+        Ok(Solutions { direct, indirect })
     }
 }
@@ -0,0 +1,177 @@
+use crate::{
+    error::{Error, Result},
+    iter::Iter,
+    output::{Measurements, Packet},
+    projectiles::Projectile,
+    units::{length, meter, radian, ratio, velocity, Angle, Energy, Length, Velocity},
+    vectors::{MyVector3, Vectors},
+    Numeric,
+};
+
+use nalgebra::Vector3;
+
+// Step used for finite differences when finding slope of the ground at impact (m)
+const SLOPE_DELTA: Numeric = 0.01;
+
+// Ground surface, in the shooter's frame - downrange along bearing, crossrange to the right
+// Heights are relative to the scope, the same origin used for packet positions
+pub trait Terrain {
+    fn height(&self, downrange: Length, crossrange: Length) -> Length;
+}
+
+impl<F> Terrain for F
+where
+    F: Fn(Length, Length) -> Length,
+{
+    fn height(&self, downrange: Length, crossrange: Length) -> Length {
+        self(downrange, crossrange)
+    }
+}
+
+// Regular grid of ground heights, rows downrange and columns crossrange
+// Heights between grid points are bilinear, and held at the edge value outside the grid
+#[derive(Debug, Clone)]
+pub struct HeightMap {
+    spacing: Length,    // Distance between adjacent grid points, both directions
+    crossrange: Length, // Crossrange of first column, grid is usually centered on line of fire
+    heights: Vec<Vec<Length>>,
+}
+impl HeightMap {
+    pub fn new(spacing: Length, crossrange: Length, heights: Vec<Vec<Length>>) -> Result<Self> {
+        if spacing <= Length::new::<meter>(0.0) {
+            Err(Error::PositiveExpected(spacing.get::<meter>()))
+        } else if !heights.first().is_some_and(|first| {
+            !first.is_empty() && heights.iter().all(|row| row.len() == first.len())
+        }) {
+            Err(Error::GridShape)
+        } else {
+            Ok(Self {
+                spacing,
+                crossrange,
+                heights,
+            })
+        }
+    }
+    // Index of the lower grid line and fraction toward the next, clamped to the grid
+    fn locate(&self, value: Length, len: usize) -> (usize, Numeric) {
+        let position = (value / self.spacing).get::<ratio::ratio>().max(0.0);
+        let index = position.floor() as usize;
+        if index + 1 >= len {
+            (len - 1, 0.0)
+        } else {
+            (index, position - index as Numeric)
+        }
+    }
+}
+impl Terrain for HeightMap {
+    fn height(&self, downrange: Length, crossrange: Length) -> Length {
+        let (row, dr) = self.locate(downrange, self.heights.len());
+        let (col, dc) = self.locate(crossrange - self.crossrange, self.heights[0].len());
+        let next_row = (row + 1).min(self.heights.len() - 1);
+        let next_col = (col + 1).min(self.heights[0].len() - 1);
+
+        let near = self.heights[row][col] * (1.0 - dc) + self.heights[row][next_col] * dc;
+        let far = self.heights[next_row][col] * (1.0 - dc) + self.heights[next_row][next_col] * dc;
+        near * (1.0 - dr) + far * dr
+    }
+}
+
+// Point where trajectory meets the ground
+#[derive(Debug)]
+pub struct Impact<'t, T> {
+    packet: Packet<'t, T>, // State at impact, interpolated between steps
+    angle: Angle,          // Angle between flight path and ground surface
+}
+impl<'t, T> Impact<'t, T>
+where
+    T: Projectile,
+{
+    pub fn packet(&self) -> &Packet<'t, T> {
+        &self.packet
+    }
+    pub fn angle(&self) -> Angle {
+        self.angle
+    }
+    pub fn downrange(&self) -> Length {
+        self.packet.level_position().get_x()
+    }
+    pub fn crossrange(&self) -> Length {
+        self.packet.level_position().get_z()
+    }
+    pub fn height(&self) -> Length {
+        self.packet.level_position().get_y()
+    }
+    pub fn velocity(&self) -> Velocity {
+        self.packet.velocity()
+    }
+    pub fn energy(&self) -> Energy {
+        self.packet.energy()
+    }
+}
+
+impl<'t, T> Packet<'t, T>
+where
+    T: Projectile,
+{
+    // Position with shooter's bearing removed, but not line of sight - used for terrain lookups
+    fn level_position(&self) -> MyVector3<length::Dimension> {
+        self.position.pivot_y(-self.simulation.shooter.yaw())
+    }
+    fn level_velocity(&self) -> MyVector3<velocity::Dimension> {
+        self.velocity.pivot_y(-self.simulation.shooter.yaw())
+    }
+    // Height above the ground directly beneath (or above) the projectile
+    fn clearance<M>(&self, terrain: &M) -> Length
+    where
+        M: Terrain,
+    {
+        let position = self.level_position();
+        position.get_y() - terrain.height(position.get_x(), position.get_z())
+    }
+}
+
+impl<'t, T> Iter<'t, T>
+where
+    T: Projectile,
+{
+    // First point where the projectile drops below the ground, interpolated between steps
+    // Only going from above (or on) the ground to below counts, so a trajectory starting below
+    // the surface doesn't 'impact' where it comes back out
+    // Returns None if iteration ends first - use stop conditions to bound flights that never land
+    pub fn impact<M>(self, terrain: &M) -> Option<Impact<'t, T>>
+    where
+        M: Terrain,
+    {
+        let clearance = |p: &Packet<T>| p.clearance(terrain).get::<meter>();
+        let mut prev: Option<Packet<T>> = None;
+        for packet in self {
+            if let Some(prev) = prev {
+                if clearance(&prev) >= 0.0 && clearance(&packet) < 0.0 {
+                    let packet = prev.crossing(&packet, &clearance);
+                    let angle = surface_angle(&packet, terrain);
+                    return Some(Impact { packet, angle });
+                }
+            }
+            prev = Some(packet);
+        }
+        None
+    }
+}
+
+// Angle between velocity and the local ground plane, from the ground's slope at impact
+fn surface_angle<T, M>(packet: &Packet<T>, terrain: &M) -> Angle
+where
+    T: Projectile,
+    M: Terrain,
+{
+    let position = packet.level_position();
+    let (x, z) = (position.get_x(), position.get_z());
+    let delta = Length::new::<meter>(SLOPE_DELTA);
+
+    let dx = (terrain.height(x + delta, z) - terrain.height(x - delta, z)) / (delta * 2.0);
+    let dz = (terrain.height(x, z + delta) - terrain.height(x, z - delta)) / (delta * 2.0);
+    let normal = Vector3::new(-dx.get::<ratio::ratio>(), 1.0, -dz.get::<ratio::ratio>());
+    let velocity = Vector3::from(packet.level_velocity());
+
+    Angle::new::<radian>((velocity.dot(&normal).abs() / (velocity.norm() * normal.norm())).asin())
+}
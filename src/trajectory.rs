@@ -0,0 +1,111 @@
+use crate::{
+    iter::{Iter, Termination},
+    output::{Measurements, Packet},
+    projectiles::Projectile,
+    simulation::Simulation,
+    units::{length, meter, second, velocity, Length, Time},
+    vectors::MyVector3,
+    Numeric,
+};
+
+// Stored state of a single step
+#[derive(Debug, Clone, Copy)]
+struct Step {
+    time: Time,                               // Position in time (s)
+    position: MyVector3<length::Dimension>,   // Position (m)
+    velocity: MyVector3<velocity::Dimension>, // Velocity (m/s)
+}
+
+// Dense output of a completed simulation run
+// Keeps its own copy of the simulation, so it is not tied to the borrow used to build it
+// Packets returned are interpolated between stored steps, without running the simulation again
+#[derive(Debug, Clone)]
+pub struct Trajectory<T> {
+    simulation: Simulation<T>,
+    steps: Vec<Step>,
+    termination: Option<Termination>,
+}
+
+impl<T> Iter<'_, T>
+where
+    T: Projectile + Clone,
+{
+    // Run iteration to completion, storing every step
+    // Use stop conditions to bound the flight - memory grows with the number of steps
+    pub fn trajectory(mut self) -> Trajectory<T> {
+        let steps = (&mut self)
+            .map(|p| Step {
+                time: p.time,
+                position: p.position,
+                velocity: p.velocity,
+            })
+            .collect();
+        Trajectory {
+            simulation: self.simulation.clone(),
+            steps,
+            termination: self.termination(),
+        }
+    }
+}
+
+impl<T> Trajectory<T>
+where
+    T: Projectile,
+{
+    pub fn simulation(&self) -> &Simulation<T> {
+        &self.simulation
+    }
+    pub fn termination(&self) -> Option<Termination> {
+        self.termination
+    }
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+    // Stored steps, as they were produced by iteration
+    pub fn packets(&self) -> impl Iterator<Item = Packet<'_, T>> + '_ {
+        self.steps.iter().map(move |step| self.packet(step))
+    }
+    // State at the given time of flight, None outside the stored flight
+    pub fn at_time(&self, time: Time) -> Option<Packet<'_, T>> {
+        self.search(|p| (p.time() - time).get::<second>())
+    }
+    // State at the given distance along line of sight, None outside the stored flight
+    // Assumes distance increases through the flight, which holds unless fired near vertical
+    pub fn at_distance(&self, distance: Length) -> Option<Packet<'_, T>> {
+        self.search(|p| (p.distance() - distance).get::<meter>())
+    }
+    fn packet(&self, step: &Step) -> Packet<'_, T> {
+        Packet {
+            simulation: &self.simulation,
+            time: step.time,
+            position: step.position,
+            velocity: step.velocity,
+        }
+    }
+    // Binary search for the step bracketing a zero of an increasing residual, then interpolate
+    fn search<F>(&self, residual: F) -> Option<Packet<'_, T>>
+    where
+        F: Fn(&Packet<T>) -> Numeric,
+    {
+        let index = self
+            .steps
+            .partition_point(|step| residual(&self.packet(step)) < 0.0);
+        if index == self.steps.len() {
+            None
+        } else if index == 0 {
+            let first = self.packet(&self.steps[0]);
+            if residual(&first) == 0.0 {
+                Some(first)
+            } else {
+                None
+            }
+        } else {
+            let prev = self.packet(&self.steps[index - 1]);
+            let next = self.packet(&self.steps[index]);
+            Some(prev.crossing(&next, &residual))
+        }
+    }
+}
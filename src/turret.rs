@@ -0,0 +1,75 @@
+use crate::{
+    error::{Error, Result},
+    simulation::{Simulation, Turret},
+    units::{ratio, Angle, Length},
+};
+
+// Correction on a single turret, rounded to whole clicks
+#[derive(Debug, Clone, Copy)]
+pub struct Clicks {
+    count: i64,      // Clicks to dial, positive is up or right
+    dialed: Angle,   // Actual movement from those clicks, after tracking scale
+    residual: Angle, // Correction left over after rounding
+    miss: Length,    // Residual as an offset at the target
+}
+impl Clicks {
+    pub fn count(&self) -> i64 {
+        self.count
+    }
+    pub fn dialed(&self) -> Angle {
+        self.dialed
+    }
+    pub fn residual(&self) -> Angle {
+        self.residual
+    }
+    pub fn miss(&self) -> Length {
+        self.miss
+    }
+}
+
+// Clicks on both turrets for a single correction
+#[derive(Debug, Clone, Copy)]
+pub struct Dial {
+    elevation: Clicks,
+    windage: Clicks,
+}
+impl Dial {
+    pub fn elevation(&self) -> Clicks {
+        self.elevation
+    }
+    pub fn windage(&self) -> Clicks {
+        self.windage
+    }
+}
+
+impl Turret {
+    // Nearest whole clicks to the correction, allowing for tracking scale
+    // Travel limits the clicks dialed from zero, so is checked before scaling
+    fn dial(&self, correction: Angle, distance: Length) -> Result<Clicks> {
+        let step = self.click * self.scale;
+        let count = (correction / step).get::<ratio::ratio>().round();
+        if let Some(travel) = self.travel {
+            if (self.click * count).abs() > travel {
+                return Err(Error::TurretTravel { correction, travel });
+            }
+        }
+        let dialed = step * count;
+        let residual = correction - dialed;
+        Ok(Clicks {
+            count: count as i64,
+            dialed,
+            residual,
+            miss: distance * residual.tan().get::<ratio::ratio>(),
+        })
+    }
+}
+
+impl<T> Simulation<T> {
+    // Clicks to dial for corrections at distance, ie, from a firing solution or range card
+    pub fn dial(&self, elevation: Angle, windage: Angle, distance: Length) -> Result<Dial> {
+        Ok(Dial {
+            elevation: self.scope.elevation.dial(elevation, distance)?,
+            windage: self.scope.windage.dial(windage, distance)?,
+        })
+    }
+}
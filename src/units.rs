@@ -18,6 +18,7 @@ pub use uom::{
         mass::{self, grain, kilogram, pound},
         mass_density::{self, kilogram_per_cubic_meter},
         molar_mass::{self},
+        momentum::{self, kilogram_meter_per_second},
         pressure::{self, inch_of_mercury, pascal},
         ratio::{self},
         thermodynamic_temperature::{
@@ -33,5 +34,28 @@ pub(crate) use uom::{
     typenum, Conversion,
 };
 
+// Scopes measure in milliradians - uom's own mil is the NATO mil (1/6400 of a revolution)
+pub mod milliradian {
+    unit! {
+        system: uom::si;
+        quantity: uom::si::angle;
+
+        @milliradian: 1.0E-3; "mrad", "milliradian", "milliradians";
+    }
+}
+pub use self::milliradian::milliradian as mil;
+
+// Angle as uom reads it, ie, "0.25 '", apart from "mil" - uom's is the NATO mil, but scopes mean
+// milliradians, same as mil above - and "mrad", which uom doesn't know at all
+pub fn parse_angle(value: &str) -> Result<Angle, ParseQuantityError> {
+    match value.split_once(' ') {
+        Some((number, "mil" | "mrad")) => number
+            .parse()
+            .map(Angle::new::<mil>)
+            .map_err(|_| ParseQuantityError::ValueParseError),
+        _ => value.parse(),
+    }
+}
+
 pub(crate) type MyUnits = SI<Numeric>;
 pub(crate) type MyQuantity<D> = Quantity<D, MyUnits, Numeric>;
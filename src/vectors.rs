@@ -1,3 +1,4 @@
+#[allow(unused_imports)]
 pub use self::{add::*, add_assign::*, mul::*, mul_assign::*};
 use crate::{
     units::{radian, Angle, Conversion, Dimension, MyUnits, Quantity, Units, ISQ},